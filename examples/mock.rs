@@ -0,0 +1,89 @@
+#![deny(warnings)]
+
+extern crate github;
+
+// Example of testing requests offline, using a local mock server
+
+use github::http::Method;
+use github::mock::{events_json, issue_event_json, MockServer, MockResponse};
+use github::pagination::Paginator;
+use github::activity::events::*;
+
+fn main() {
+    // The server stops listening once it goes out of scope.
+    let server = MockServer::start().unwrap();
+    let client = &server.client("glendc");
+    // The json of an event or issue event can be built from an id.
+    let events = events_json(&[2489651045]);
+    let issue_event = issue_event_json(1);
+
+    // Each events function can be exercised by serving its route.
+    let routes = [
+        "/events",
+        "/repos/glendc/github-rust/events",
+        "/users/glendc/received_events",
+        "/users/glendc/received_events/public",
+        "/users/glendc/events",
+        "/users/glendc/events/public",
+        "/networks/glendc/github-rust/events",
+        "/orgs/rust-lang/events",
+        "/users/glendc/events/orgs/rust-lang",
+    ];
+    for route in routes.iter() {
+        server.route(Method::Get, route, MockResponse::json(&events));
+    }
+    let issue_events = format!("[{}]", issue_event);
+    server.route(Method::Get, "/repos/glendc/github-rust/issues/events", MockResponse::json(&issue_events));
+    server.route(Method::Get, "/repos/glendc/github-rust/issues/42/events", MockResponse::json(&issue_events));
+    server.route(Method::Get, "/repos/glendc/github-rust/issues/events/1", MockResponse::json(&issue_event));
+
+    let results = vec![
+        ("list_events", list_events(client)),
+        ("list_my_repo_events", list_my_repo_events(client, "github-rust")),
+        ("list_my_received_events", list_my_received_events(client)),
+        ("list_my_received_public_events", list_my_received_public_events(client)),
+        ("list_my_events", list_my_events(client)),
+        ("list_my_public_user_events", list_my_public_user_events(client)),
+        ("list_my_public_network_repo_events", list_my_public_network_repo_events(client, "github-rust")),
+        ("list_public_organisation_events", list_public_organisation_events(client, "rust-lang")),
+        ("list_my_organisation_events", list_my_organisation_events(client, "rust-lang")),
+    ];
+    for (name, result) in results {
+        match result {
+            Ok((events, resp)) => println!("{} => {} event(s), {} of {} requests remaining",
+                name, events.len(), resp.rate.remaining, resp.rate.limit),
+            Err(err) => println!("{} => {}", name, err),
+        }
+    }
+    match list_my_repo_issue_events(client, "github-rust") {
+        Ok((events, _)) => println!("list_my_repo_issue_events => {} event(s)", events.len()),
+        Err(err) => println!("list_my_repo_issue_events => {}", err),
+    }
+    match list_my_issue_events(client, "github-rust", 42) {
+        Ok((events, _)) => println!("list_my_issue_events => {} event(s)", events.len()),
+        Err(err) => println!("list_my_issue_events => {}", err),
+    }
+    match get_my_issue_event(client, "github-rust", 1) {
+        Ok((event, _)) => println!("get_my_issue_event => {} at {}", event.event, event.created_at),
+        Err(err) => println!("get_my_issue_event => {}", err),
+    }
+
+    // Pagination can be tested by linking pages to one another.
+    let next = format!("{}repos/glendc/pages/events?page=2", server.url());
+    server.route(Method::Get, "/repos/glendc/pages/events",
+        MockResponse::json(&events).link("next", &next).link("last", &next));
+    server.route(Method::Get, "/repos/glendc/pages/events?page=2", MockResponse::json(&events));
+    let count = Paginator::from_page(client, list_repo_events(client, "glendc", "pages")).count();
+    println!("paginated list_repo_events => {} event(s) over 2 pages", count);
+
+    // Unknown routes are given a 404, just like GitHub would.
+    if let Err(err) = list_my_repo_events(client, "42") {
+        println!("list_my_repo_events failed: {}", err);
+    }
+
+    // All requests received by the server can be inspected.
+    for request in server.requests() {
+        println!("{} {} (User-Agent: {})", request.method, request.path,
+            request.header("User-Agent").unwrap_or(""));
+    }
+}
@@ -6,6 +6,7 @@ extern crate github;
 
 use github::Client;
 use github::error::*;
+use github::pagination::Paginator;
 use github::activity::events::*;
 
 fn main() {
@@ -21,8 +22,8 @@ fn main() {
             println!("listing public events succesfull, we have {} requsts remaining of {}. Limit resets @ {}...",
                 resp.rate.remaining, resp.rate.limit, resp.rate.reset);
             for event in events {
-                println!("event #{} at {} by {}...",
-                    event.id, event.created_at, event.actor.login);
+                println!("{} #{} at {} by {}...",
+                    event.kind.name(), event.id, event.created_at, event.actor.login);
             }
         }
         Err(err) => {
@@ -35,6 +36,18 @@ fn main() {
         }
     }
 
+    // An example of walking over multiple pages of events,
+    // the next page is only requested once all previous events are consumed.
+    println!("# Example: paginated list_repo_events for `rust-lang/rust`");
+    let events = Paginator::from_page(client, list_repo_events(client, "rust-lang", "rust"))
+        .max_pages(3);
+    for event in events {
+        match event {
+            Ok(event) => println!("event #{} by {}...", event.id, event.actor.login),
+            Err(err) => println!("list_repo_events failed: {}", err),
+        }
+    }
+
     // An example of a request that we expect to fail,
     // because the repository doesn't exist (404).
     println!("# Example: failed list_my_repo_events");
@@ -57,7 +70,8 @@ fn main() {
         println!("Debug info => {:?}", resp);
         for event in events {
             println!("event ({}) #{} at {} by {}...",
-                event.event, event.id, event.created_at, event.actor.login);
+                event.event, event.id, event.created_at,
+                event.actor.as_ref().map_or("ghost", |actor| &actor.login[..]));
         }
     }
 }
@@ -2,23 +2,71 @@ use Client;
 
 use activity::EventReturnType;
 use activity::IssueEventReturnType;
+use activity::SingleIssueEventReturnType;
 
 /// Documentation References:
 /// https://developer.github.com/v3/activity/events/
 
+// The urls listing the events are shared with the `EventPoller`.
+
+/// The url listing the public events.
+pub(crate) fn events_url(client: &Client) -> String {
+    format!("{}events", client.base_url)
+}
+
+/// The url listing the repository events.
+pub(crate) fn repo_events_url(client: &Client, user: &str, repo: &str) -> String {
+    format!("{}repos/{}/{}/events", client.base_url, user, repo)
+}
+
+/// The url listing the events that a user has received.
+pub(crate) fn received_user_events_url(client: &Client, user: &str) -> String {
+    format!("{}users/{}/received_events", client.base_url, user)
+}
+
+/// The url listing the public events that a user has received.
+pub(crate) fn received_public_user_events_url(client: &Client, user: &str) -> String {
+    format!("{}users/{}/received_events/public", client.base_url, user)
+}
+
+/// The url listing the events performed by a user.
+pub(crate) fn user_events_url(client: &Client, user: &str) -> String {
+    format!("{}users/{}/events", client.base_url, user)
+}
+
+/// The url listing the public events performed by a user.
+pub(crate) fn public_user_events_url(client: &Client, user: &str) -> String {
+    format!("{}users/{}/events/public", client.base_url, user)
+}
+
+/// The url listing the public events for a network of repositories.
+pub(crate) fn public_network_repo_events_url(client: &Client, user: &str, repo: &str) -> String {
+    format!("{}networks/{}/{}/events", client.base_url, user, repo)
+}
+
+/// The url listing the public events for an organization.
+pub(crate) fn public_organisation_events_url(client: &Client, org: &str) -> String {
+    format!("{}orgs/{}/events", client.base_url, org)
+}
+
+/// The url listing the events for an organization.
+pub(crate) fn organisation_events_url(client: &Client, user: &str, org: &str) -> String {
+    format!("{}users/{}/events/orgs/{}", client.base_url, user, org)
+}
+
 /// List public events.
 pub fn list_events(client: &Client) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}events", client.base_url),
+        client,
+        &events_url(client),
         None)
 }
 
 /// List repository events.
 pub fn list_repo_events(client: &Client, user: &str, repo: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}repos/{}/{}/events", client.base_url, user, repo),
+        client,
+        &repo_events_url(client, user, repo),
         None)
 }
 
@@ -35,8 +83,8 @@ pub fn list_my_repo_events(client: &Client, repo: &str) -> EventReturnType {
 /// Otherwise, you’ll only see public events.
 pub fn list_received_user_events(client: &Client, user: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}users/{}/received_events", client.base_url, user),
+        client,
+        &received_user_events_url(client, user),
         None)
 }
 
@@ -48,8 +96,8 @@ pub fn list_my_received_events(client: &Client) -> EventReturnType {
 /// List public events that a user has received.
 pub fn list_received_public_user_events(client: &Client, user: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}users/{}/received_events/public", client.base_url, user),
+        client,
+        &received_public_user_events_url(client, user),
         None)
 }
 
@@ -64,8 +112,8 @@ pub fn list_my_received_public_events(client: &Client) -> EventReturnType {
 /// Otherwise, you’ll only see public events.
 pub fn list_user_events(client: &Client, user: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}users/{}/events", client.base_url, user),
+        client,
+        &user_events_url(client, user),
         None)
 }
 
@@ -77,8 +125,8 @@ pub fn list_my_events(client: &Client) -> EventReturnType {
 /// List public events performed by a user.
 pub fn list_public_user_events(client: &Client, user: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}users/{}/events/public", client.base_url, user),
+        client,
+        &public_user_events_url(client, user),
         None)
 }
 
@@ -91,8 +139,8 @@ pub fn list_my_public_user_events(client: &Client) -> EventReturnType {
 /// List public events for a network of repositories.
 pub fn list_public_network_repo_events(client: &Client, user: &str, repo: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}networks/{}/{}/events", client.base_url, user, repo),
+        client,
+        &public_network_repo_events_url(client, user, repo),
         None)
 }
 
@@ -105,8 +153,8 @@ pub fn list_my_public_network_repo_events(client: &Client, repo: &str) -> EventR
 /// List public events for an organization.
 pub fn list_public_organisation_events(client: &Client, org: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}orgs/{}/events", client.base_url, org),
+        client,
+        &public_organisation_events_url(client, org),
         None)
 }
 
@@ -116,8 +164,8 @@ pub fn list_public_organisation_events(client: &Client, org: &str) -> EventRetur
 /// You must be authenticated as the user to view this.
 pub fn list_organisation_events(client: &Client, user: &str, org: &str) -> EventReturnType {
     ::http::get(
-        &client.user_agent,
-        &format!("{}users/{}/events/orgs/{}", client.base_url, user, org),
+        client,
+        &organisation_events_url(client, user, org),
         None)
 }
 
@@ -133,7 +181,7 @@ pub fn list_my_organisation_events(client: &Client, org: &str) -> EventReturnTyp
 /// as documented by the GitHub Events API and represented by `IssueEventResponse`.
 pub fn list_repo_issue_events(client: &Client, user: &str, repo: &str) -> IssueEventReturnType {
     ::http::get(
-        &client.user_agent,
+        client,
         &format!("{}repos/{}/{}/issues/events", client.base_url, user, repo),
         None)
 }
@@ -143,3 +191,32 @@ pub fn list_repo_issue_events(client: &Client, user: &str, repo: &str) -> IssueE
 pub fn list_my_repo_issue_events(client: &Client, repo: &str) -> IssueEventReturnType {
     list_repo_issue_events(client, &client.user_agent, repo)
 }
+
+/// List events for a single issue of a repository,
+/// for which the `issue` field of the events isn't given.
+pub fn list_issue_events(client: &Client, user: &str, repo: &str, number: u64) -> IssueEventReturnType {
+    ::http::get(
+        client,
+        &format!("{}repos/{}/{}/issues/{}/events", client.base_url, user, repo, number),
+        None)
+}
+
+/// List events for a single issue of a repository owned by
+/// the user defined in `Client` as `user_agent`.
+pub fn list_my_issue_events(client: &Client, repo: &str, number: u64) -> IssueEventReturnType {
+    list_issue_events(client, &client.user_agent, repo, number)
+}
+
+/// Get a single issue event of a repository by its id.
+pub fn get_issue_event(client: &Client, user: &str, repo: &str, id: u64) -> SingleIssueEventReturnType {
+    ::http::get_one(
+        client,
+        &format!("{}repos/{}/{}/issues/events/{}", client.base_url, user, repo, id),
+        None)
+}
+
+/// Get a single issue event of a repository owned by
+/// the user defined in `Client` as `user_agent`.
+pub fn get_my_issue_event(client: &Client, repo: &str, id: u64) -> SingleIssueEventReturnType {
+    get_issue_event(client, &client.user_agent, repo, id)
+}
@@ -0,0 +1,508 @@
+use Client;
+
+use error::*;
+use http::Method;
+use response::Response;
+
+use chrono::{DateTime, UTC};
+
+use std::fmt;
+use std::str;
+use std::error::Error;
+
+/// Documentation References:
+/// https://developer.github.com/v3/activity/feeds/
+/// https://tools.ietf.org/html/rfc4287
+
+/// The Accept header value used to request a feed as Atom.
+static ATOM_ACCEPT_HEADER: &'static str = "application/atom+xml";
+
+/// `Feeds` contains the urls of all the Atom feeds available to the
+/// authenticated user. The `current_user_*` urls are only given when
+/// the client is authenticated, and contain a token to read the private feeds.
+#[derive(Debug, RustcDecodable)]
+pub struct Feeds {
+    /// the GitHub global public timeline
+    pub timeline_url: String,
+    /// the public timeline of any user, given as a template, see `user_url_for`
+    pub user_url: String,
+    /// the public timeline of the authenticated user
+    pub current_user_public_url: Option<String>,
+    /// the private timeline of the authenticated user
+    pub current_user_url: Option<String>,
+    /// the private timeline of the activity created by the authenticated user
+    pub current_user_actor_url: Option<String>,
+    /// the private timeline of an organization of the authenticated user,
+    /// given as a template with an `{org}` placeholder
+    pub current_user_organization_url: Option<String>,
+    /// the private timelines of all organizations of the authenticated user
+    pub current_user_organization_urls: Option<Vec<String>>,
+    /// the security advisories published on GitHub
+    pub security_advisories_url: Option<String>,
+}
+
+impl Feeds {
+    /// The url of the public timeline of the given user.
+    pub fn user_url_for(&self, user: &str) -> String {
+        self.user_url.replace("{user}", user)
+    }
+
+    /// The url of the private timeline of the given organization,
+    /// in case the client is authenticated.
+    pub fn current_user_organization_url_for(&self, org: &str) -> Option<String> {
+        self.current_user_organization_url.as_ref().map(|url| url.replace("{org}", org))
+    }
+}
+
+/// `AtomFeed` is an Atom feed, such as any of the feeds listed by `Feeds`.
+#[derive(Debug)]
+pub struct AtomFeed {
+    pub id: String,
+    pub title: String,
+    pub updated: DateTime<UTC>,
+    /// the url of the html page represented by this feed
+    pub url: Option<String>,
+    pub entries: Vec<AtomEntry>,
+}
+
+/// `AtomEntry` is a single entry of an Atom feed,
+/// which in general represents a single event.
+#[derive(Debug)]
+pub struct AtomEntry {
+    pub id: String,
+    pub title: String,
+    pub published: Option<DateTime<UTC>>,
+    pub updated: DateTime<UTC>,
+    /// the url of the html page represented by this entry
+    pub url: Option<String>,
+    pub author: Option<AtomAuthor>,
+    /// the content of the entry, which is html for the GitHub timelines
+    pub content: Option<String>,
+    /// the url of the avatar of the author, given as `media:thumbnail`
+    pub thumbnail: Option<String>,
+}
+
+impl AtomEntry {
+    /// The type of the event represented by this entry, such as `PushEvent`,
+    /// as given in an id like `tag:github.com,2008:PushEvent/1234567890`.
+    pub fn event_name(&self) -> Option<&str> {
+        let tag = match self.id.rfind(':') {
+            Some(index) => &self.id[index + 1..],
+            None => return None,
+        };
+        tag.split('/').next().and_then(|name| if name.is_empty() { None } else { Some(name) })
+    }
+}
+
+/// `AtomAuthor` is the author of an Atom entry.
+#[derive(Debug)]
+pub struct AtomAuthor {
+    pub name: String,
+    pub email: Option<String>,
+    pub uri: Option<String>,
+}
+
+/// `FeedError` is the error given when a feed can't be parsed,
+/// because it isn't well-formed xml or isn't a valid Atom feed.
+#[derive(Debug)]
+pub struct FeedError {
+    pub msg: String,
+    /// the byte offset in the feed where the error was found, if any
+    pub position: Option<usize>,
+}
+
+/// Allowing `FeedError` to be printed via `{}` in a controlled manner.
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "Invalid feed: {} at byte {}", self.msg, position),
+            None => write!(f, "Invalid feed: {}", self.msg),
+        }
+    }
+}
+
+impl Error for FeedError {}
+
+/// `FeedsReturnType` is the return type for the request listing the feeds.
+pub type FeedsReturnType = Result<(Feeds, Response), ClientError>;
+/// `AtomFeedReturnType` is the return type for the requests of a single feed.
+pub type AtomFeedReturnType = Result<(AtomFeed, Response), ClientError>;
+
+/// List the feeds available to the authenticated user.
+pub fn get_feeds(client: &Client) -> FeedsReturnType {
+    ::http::get_one(
+        client,
+        &format!("{}feeds", client.base_url),
+        None)
+}
+
+/// Get and parse the Atom feed at the given url, such as any of the urls given by
+/// `get_feeds`. A relative url is resolved against the base url of the `Client`.
+/// The credentials of the `Client` are only sent for urls starting with its base url,
+/// the private feeds on github.com are authenticated by the token in their url instead.
+pub fn get_feed(client: &Client, url: &str) -> AtomFeedReturnType {
+    let url = if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("{}{}", client.base_url, url.trim_left_matches('/'))
+    };
+    ::http::get_raw(client, &url, Some(vec![("Accept", ATOM_ACCEPT_HEADER)]))
+        .and_then(|(raw_body, resp)| {
+            let raw_body = match str::from_utf8(&raw_body) {
+                Ok(raw_body) => raw_body,
+                Err(e) => return InternalError::from_cause(e),
+            };
+            match parse_feed(raw_body) {
+                Ok(feed) => Ok((feed, resp)),
+                Err(e) => InternalError::from_cause(e),
+            }
+        })
+        .map_err(|e| e.for_request(Method::Get, &url))
+}
+
+/// Get the GitHub global public timeline.
+pub fn get_timeline(client: &Client) -> AtomFeedReturnType {
+    let (feeds, _) = try!(get_feeds(client));
+    get_feed(client, &feeds.timeline_url)
+}
+
+/// Get the public timeline of a user.
+pub fn get_user_feed(client: &Client, user: &str) -> AtomFeedReturnType {
+    let (feeds, _) = try!(get_feeds(client));
+    get_feed(client, &feeds.user_url_for(user))
+}
+
+/// Parse an Atom feed, such as the body of any of the feeds listed by `Feeds`.
+pub fn parse_feed(raw: &str) -> Result<AtomFeed, FeedError> {
+    let root = try!(Parser::new(raw).parse_document());
+    if root.name != "feed" {
+        return Err(FeedError {
+            msg: format!("expected a feed element, found {}", root.name),
+            position: None,
+        });
+    }
+
+    let mut entries = Vec::new();
+    for entry in root.children_named("entry") {
+        entries.push(AtomEntry {
+            id: try!(entry.required_text("id")),
+            title: try!(entry.required_text("title")),
+            published: try!(entry.optional_datetime("published")),
+            updated: try!(entry.required_datetime("updated")),
+            url: entry.alternate_url(),
+            author: match entry.child("author") {
+                Some(author) => Some(AtomAuthor {
+                    name: try!(author.required_text("name")),
+                    email: author.child("email").map(Element::text),
+                    uri: author.child("uri").map(Element::text),
+                }),
+                None => None,
+            },
+            content: entry.child("content").map(Element::text),
+            thumbnail: entry.child("media:thumbnail")
+                .and_then(|thumbnail| thumbnail.attr("url"))
+                .map(str::to_string),
+        });
+    }
+
+    Ok(AtomFeed {
+        id: try!(root.required_text("id")),
+        title: try!(root.required_text("title")),
+        updated: try!(root.required_datetime("updated")),
+        url: root.alternate_url(),
+        entries: entries,
+    })
+}
+
+/// `Element` is an xml element, which is all that's needed from xml to read
+/// an Atom feed. Text and CDATA sections are kept as text nodes.
+#[derive(Debug)]
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+/// `Node` is the content of an xml element.
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    /// The value of the attribute with the given name, if any.
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|&&(ref key, _)| key == name).map(|&(_, ref value)| &value[..])
+    }
+
+    /// The first child element with the given name, if any.
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children_named(name).into_iter().next()
+    }
+
+    /// All child elements with the given name.
+    fn children_named(&self, name: &str) -> Vec<&Element> {
+        self.children.iter()
+            .filter_map(|node| match *node {
+                Node::Element(ref element) if element.name == name => Some(element),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All text of this element, without the surrounding whitespace.
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.children {
+            if let Node::Text(ref value) = *node {
+                text.push_str(value);
+            }
+        }
+        text.trim().to_string()
+    }
+
+    /// The text of the required child element with the given name.
+    fn required_text(&self, name: &str) -> Result<String, FeedError> {
+        match self.child(name) {
+            Some(child) => Ok(child.text()),
+            None => Err(FeedError {
+                msg: format!("missing {} element in {}", name, self.name),
+                position: None,
+            }),
+        }
+    }
+
+    /// The timestamp of the optional child element with the given name,
+    /// which is formatted as defined by RFC 3339, as for all Atom timestamps.
+    fn optional_datetime(&self, name: &str) -> Result<Option<DateTime<UTC>>, FeedError> {
+        let raw = match self.child(name) {
+            Some(child) => child.text(),
+            None => return Ok(None),
+        };
+        match DateTime::parse_from_rfc3339(&raw) {
+            Ok(datetime) => Ok(Some(datetime.with_timezone(&UTC))),
+            Err(e) => Err(FeedError {
+                msg: format!("invalid timestamp {}: {}", raw, e),
+                position: None,
+            }),
+        }
+    }
+
+    /// The timestamp of the required child element with the given name.
+    fn required_datetime(&self, name: &str) -> Result<DateTime<UTC>, FeedError> {
+        match try!(self.optional_datetime(name)) {
+            Some(datetime) => Ok(datetime),
+            None => Err(FeedError {
+                msg: format!("missing {} element in {}", name, self.name),
+                position: None,
+            }),
+        }
+    }
+
+    /// The url of the alternate link, which is the default relation of a link.
+    fn alternate_url(&self) -> Option<String> {
+        self.children_named("link").into_iter()
+            .find(|link| link.attr("rel").map_or(true, |rel| rel == "alternate"))
+            .and_then(|link| link.attr("href"))
+            .map(str::to_string)
+    }
+}
+
+/// `Parser` is a minimal xml parser, reading a document into `Element`s.
+/// Processing instructions, comments and doctype declarations are skipped,
+/// and namespace prefixes are kept as part of the names.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Parser<'a> {
+        Parser {
+            src: src,
+            pos: 0,
+        }
+    }
+
+    /// Construct an error found at the current position.
+    fn error<T>(&self, msg: &str) -> Result<T, FeedError> {
+        Err(FeedError {
+            msg: msg.to_string(),
+            position: Some(self.pos),
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_left().len();
+    }
+
+    /// Consume the given token in case the input continues with it.
+    fn consume(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the input up to the given token, which is consumed as well.
+    fn consume_until(&mut self, token: &str) -> Result<&'a str, FeedError> {
+        match self.rest().find(token) {
+            Some(index) => {
+                let value = &self.rest()[..index];
+                self.pos += index + token.len();
+                Ok(value)
+            }
+            None => self.error(&format!("expected {}", token)),
+        }
+    }
+
+    /// Consume a tag or attribute name.
+    fn consume_name(&mut self) -> Result<&'a str, FeedError> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| c.is_whitespace() || c == '=' || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return self.error("expected a name");
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// Skip the markup that isn't an element, returning true when anything was skipped.
+    fn skip_misc(&mut self) -> Result<bool, FeedError> {
+        if self.consume("<?") {
+            try!(self.consume_until("?>"));
+        } else if self.consume("<!--") {
+            try!(self.consume_until("-->"));
+        } else if !self.rest().starts_with("<![CDATA[") && self.consume("<!") {
+            try!(self.consume_until(">"));
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Parse a document, returning its root element.
+    fn parse_document(&mut self) -> Result<Element, FeedError> {
+        // Skipping the xml declaration and anything else before the root element.
+        loop {
+            self.skip_whitespace();
+            if !try!(self.skip_misc()) {
+                break;
+            }
+        }
+        if !self.consume("<") {
+            return self.error("expected the root element");
+        }
+        self.parse_element()
+    }
+
+    /// Parse an element, of which the opening `<` was consumed already.
+    fn parse_element(&mut self) -> Result<Element, FeedError> {
+        let mut element = Element {
+            name: try!(self.consume_name()).to_string(),
+            attrs: Vec::new(),
+            children: Vec::new(),
+        };
+
+        // Parsing the attributes, up to the end of the start tag.
+        loop {
+            self.skip_whitespace();
+            if self.consume("/>") {
+                return Ok(element);
+            }
+            if self.consume(">") {
+                break;
+            }
+            let name = try!(self.consume_name()).to_string();
+            self.skip_whitespace();
+            if !self.consume("=") {
+                return self.error("expected =");
+            }
+            self.skip_whitespace();
+            let quote = if self.consume("\"") {
+                "\""
+            } else if self.consume("'") {
+                "'"
+            } else {
+                return self.error("expected a quoted attribute value");
+            };
+            let value = try!(self.consume_until(quote));
+            element.attrs.push((name, try!(self.unescape(value))));
+        }
+
+        // Parsing the content, up to the end tag.
+        loop {
+            if self.consume("</") {
+                let name = try!(self.consume_name());
+                if name != element.name {
+                    return self.error(&format!("expected </{}>", element.name));
+                }
+                self.skip_whitespace();
+                if !self.consume(">") {
+                    return self.error("expected >");
+                }
+                return Ok(element);
+            }
+            if self.consume("<![CDATA[") {
+                let text = try!(self.consume_until("]]>"));
+                element.children.push(Node::Text(text.to_string()));
+            } else if try!(self.skip_misc()) {
+                continue;
+            } else if self.consume("<") {
+                element.children.push(Node::Element(try!(self.parse_element())));
+            } else {
+                let rest = self.rest();
+                if rest.is_empty() {
+                    return self.error(&format!("expected </{}>", element.name));
+                }
+                let len = rest.find('<').unwrap_or(rest.len());
+                self.pos += len;
+                element.children.push(Node::Text(try!(self.unescape(&rest[..len]))));
+            }
+        }
+    }
+
+    /// Replace the predefined entities and character references of xml.
+    fn unescape(&self, raw: &str) -> Result<String, FeedError> {
+        let mut value = String::with_capacity(raw.len());
+        let mut rest = raw;
+        while let Some(index) = rest.find('&') {
+            value.push_str(&rest[..index]);
+            rest = &rest[index + 1..];
+            let end = match rest.find(';') {
+                Some(end) => end,
+                None => return self.error("unterminated entity"),
+            };
+            let c = match &rest[..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                entity if entity.starts_with("#x") => {
+                    u32::from_str_radix(&entity[2..], 16).ok().and_then(::std::char::from_u32)
+                }
+                entity if entity.starts_with('#') => {
+                    entity[1..].parse().ok().and_then(::std::char::from_u32)
+                }
+                _ => None,
+            };
+            match c {
+                Some(c) => value.push(c),
+                None => return self.error(&format!("unknown entity &{};", &rest[..end])),
+            }
+            rest = &rest[end + 1..];
+        }
+        value.push_str(rest);
+        Ok(value)
+    }
+}
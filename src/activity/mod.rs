@@ -1,8 +1,14 @@
 use error::*;
-use response::Response;
+use http::FromJson;
+use response::{decode_datetime, Response};
+use self::payloads::{Issue, Label, User};
+
+use chrono::{DateTime, UTC};
 
 use rustc_serialize::Decoder;
 use rustc_serialize::Decodable;
+use rustc_serialize::json;
+use rustc_serialize::json::{DecoderError, Json};
 
 use std::fmt;
 
@@ -13,11 +19,21 @@ use std::fmt;
 /// The following structs represent this info found as a json response.
 
 /// `Repository` contains all info regarding a git repository.
+/// Only the `id`, `name` and `url` are given as part of an event,
+/// the other info is given when listing repositories, e.g. starred ones.
 #[derive(Debug, RustcDecodable)]
 pub struct Repository {
     pub id: u64,
     pub name: String,
     pub url: String,
+    pub full_name: Option<String>,
+    pub owner: Option<User>,
+    pub description: Option<String>,
+    pub html_url: Option<String>,
+    pub private: Option<bool>,
+    pub fork: Option<bool>,
+    pub stargazers_count: Option<u64>,
+    pub watchers_count: Option<u64>,
 }
 
 /// `Actor` contains all info on the user generating the event.
@@ -53,15 +69,59 @@ pub struct Organisation {
 
 /// `EventResponse` represents the response for almost
 /// all event requests found in `activity::events` with issues as an exception.
-#[derive(Debug, RustcDecodable)]
+#[derive(Debug)]
 pub struct EventResponse {
     pub public: bool,
     pub repo: Repository,
     pub actor: Actor,
     pub org: Option<Organisation>,
-    // todo: replace with proper time
-    pub created_at: String,
+    pub created_at: DateTime<UTC>,
     pub id: String,
+    /// the type of the event together with its payload
+    pub kind: EventKind,
+}
+
+/// `EventFields` are the fields of an `EventResponse`
+/// that can be decoded regardless of the type of the event.
+#[derive(RustcDecodable)]
+struct EventFields {
+    public: bool,
+    repo: Repository,
+    actor: Actor,
+    org: Option<Organisation>,
+    id: String,
+}
+
+/// Allowing `EventResponse` to be decoded from json values,
+/// using the `type` key to decode the `payload` key as the right `EventKind`.
+/// The `created_at` key is decoded as a timestamp.
+impl FromJson for EventResponse {
+    fn from_json(json: Json) -> Result<EventResponse, DecoderError> {
+        let mut object = match json {
+            Json::Object(object) => object,
+            json => return Err(DecoderError::ExpectedError("Object".to_string(), json.to_string())),
+        };
+        let name = match object.remove("type") {
+            Some(Json::String(name)) => name,
+            _ => return Err(DecoderError::MissingFieldError("type".to_string())),
+        };
+        let payload = object.remove("payload").unwrap_or(Json::Null);
+        let created_at = match object.remove("created_at") {
+            Some(created_at) => try!(decode_datetime(&mut json::Decoder::new(created_at))),
+            None => return Err(DecoderError::MissingFieldError("created_at".to_string())),
+        };
+        let fields: EventFields = try!(FromJson::from_json(Json::Object(object)));
+
+        Ok(EventResponse {
+            public: fields.public,
+            repo: fields.repo,
+            actor: fields.actor,
+            org: fields.org,
+            created_at: created_at,
+            id: fields.id,
+            kind: EventKind::new(&name, payload),
+        })
+    }
 }
 
 /// `IssueEventType` is an enumuration of
@@ -74,10 +134,14 @@ pub enum IssueEventType {
     Reopened,
     /// The `Actor` subscribed to receive notifications for an issue.
     Subscribed,
+    /// The `Actor` unsubscribed from receiving notifications for an issue.
+    Unsubscribed,
     /// The issue was merged by the `Actor`.
     Merged,
     /// The issue was referenced from a commit message.
     Referenced,
+    /// The issue was referenced from another issue or pull request.
+    CrossReferenced,
     /// The `Actor` was @mentioned in an issue body.
     Mentioned,
     /// The issue was assigned to the `Actor`.
@@ -98,10 +162,54 @@ pub enum IssueEventType {
     Locked,
     /// The issue was unlocked by the `Actor`.
     Unlocked,
+    /// The issue was pinned by the `Actor`.
+    Pinned,
+    /// The issue was unpinned by the `Actor`.
+    Unpinned,
+    /// The issue was transferred to another repository.
+    Transferred,
+    /// The issue was marked as a duplicate of another issue.
+    MarkedAsDuplicate,
+    /// The issue was no longer marked as a duplicate of another issue.
+    UnmarkedAsDuplicate,
+    /// The issue was created by converting a note of a project board.
+    ConvertedNoteToIssue,
+    /// The issue was added to a project board.
+    AddedToProject,
+    /// The issue was moved between the columns of a project board.
+    MovedColumnsInProject,
+    /// The issue was removed from a project board.
+    RemovedFromProject,
+    /// A comment on the issue was deleted by the `Actor`.
+    CommentDeleted,
+    /// The issue was linked to another issue or pull request.
+    Connected,
+    /// The issue was unlinked from another issue or pull request.
+    Disconnected,
+    /// A user was blocked from the organization, which hides their comments.
+    UserBlocked,
     /// The pull request’s branch was deleted.
     HeadRefDeleted,
     /// The pull request’s branch was restored.
     HeadRefRestored,
+    /// The pull request’s branch was force pushed to.
+    HeadRefForcePushed,
+    /// The base branch of the pull request was changed.
+    BaseRefChanged,
+    /// A review of the pull request was requested from the `requested_reviewer`.
+    ReviewRequested,
+    /// A requested review of the pull request was removed.
+    ReviewRequestRemoved,
+    /// A review of the pull request was dismissed.
+    ReviewDismissed,
+    /// The draft pull request was marked as ready for review.
+    ReadyForReview,
+    /// The pull request was converted to a draft.
+    ConvertedToDraft,
+    /// The pull request was deployed.
+    Deployed,
+    /// The environment of a deployment of the pull request was changed.
+    DeploymentEnvironmentChanged,
     /// `Unknown(String)` is used as a last resort when an event is unknown.
     /// This should never happen, please report/resolve the issue when it does happen.
     Unknown(String),
@@ -114,8 +222,10 @@ impl fmt::Display for IssueEventType {
             IssueEventType::Closed => "closed",
             IssueEventType::Reopened => "reopened",
             IssueEventType::Subscribed => "subscribed",
+            IssueEventType::Unsubscribed => "unsubscribed",
             IssueEventType::Merged => "merged",
             IssueEventType::Referenced => "referenced",
+            IssueEventType::CrossReferenced => "cross-referenced",
             IssueEventType::Mentioned => "mentioned",
             IssueEventType::Assigned => "assigned",
             IssueEventType::Unassigned => "unassigned",
@@ -126,8 +236,30 @@ impl fmt::Display for IssueEventType {
             IssueEventType::Renamed => "renamed",
             IssueEventType::Locked => "locked",
             IssueEventType::Unlocked => "unlocked",
+            IssueEventType::Pinned => "pinned",
+            IssueEventType::Unpinned => "unpinned",
+            IssueEventType::Transferred => "transferred",
+            IssueEventType::MarkedAsDuplicate => "marked as duplicate",
+            IssueEventType::UnmarkedAsDuplicate => "unmarked as duplicate",
+            IssueEventType::ConvertedNoteToIssue => "converted note to issue",
+            IssueEventType::AddedToProject => "added to project",
+            IssueEventType::MovedColumnsInProject => "moved columns in project",
+            IssueEventType::RemovedFromProject => "removed from project",
+            IssueEventType::CommentDeleted => "comment deleted",
+            IssueEventType::Connected => "connected",
+            IssueEventType::Disconnected => "disconnected",
+            IssueEventType::UserBlocked => "user blocked",
             IssueEventType::HeadRefDeleted => "head reference deleted",
             IssueEventType::HeadRefRestored => "head reference restored",
+            IssueEventType::HeadRefForcePushed => "head reference force pushed",
+            IssueEventType::BaseRefChanged => "base reference changed",
+            IssueEventType::ReviewRequested => "review requested",
+            IssueEventType::ReviewRequestRemoved => "review request removed",
+            IssueEventType::ReviewDismissed => "review dismissed",
+            IssueEventType::ReadyForReview => "ready for review",
+            IssueEventType::ConvertedToDraft => "converted to draft",
+            IssueEventType::Deployed => "deployed",
+            IssueEventType::DeploymentEnvironmentChanged => "deployment environment changed",
             IssueEventType::Unknown(ref s) => &s,
         };
 
@@ -144,8 +276,10 @@ impl Decodable for IssueEventType {
                 "closed" => IssueEventType::Closed,
                 "reopened" => IssueEventType::Reopened,
                 "subscribed" => IssueEventType::Subscribed,
+                "unsubscribed" => IssueEventType::Unsubscribed,
                 "merged" => IssueEventType::Merged,
                 "referenced" => IssueEventType::Referenced,
+                "cross-referenced" => IssueEventType::CrossReferenced,
                 "mentioned" => IssueEventType::Mentioned,
                 "assigned" => IssueEventType::Assigned,
                 "unassigned" => IssueEventType::Unassigned,
@@ -156,8 +290,30 @@ impl Decodable for IssueEventType {
                 "renamed" => IssueEventType::Renamed,
                 "locked" => IssueEventType::Locked,
                 "unlocked" => IssueEventType::Unlocked,
+                "pinned" => IssueEventType::Pinned,
+                "unpinned" => IssueEventType::Unpinned,
+                "transferred" => IssueEventType::Transferred,
+                "marked_as_duplicate" => IssueEventType::MarkedAsDuplicate,
+                "unmarked_as_duplicate" => IssueEventType::UnmarkedAsDuplicate,
+                "converted_note_to_issue" => IssueEventType::ConvertedNoteToIssue,
+                "added_to_project" => IssueEventType::AddedToProject,
+                "moved_columns_in_project" => IssueEventType::MovedColumnsInProject,
+                "removed_from_project" => IssueEventType::RemovedFromProject,
+                "comment_deleted" => IssueEventType::CommentDeleted,
+                "connected" => IssueEventType::Connected,
+                "disconnected" => IssueEventType::Disconnected,
+                "user_blocked" => IssueEventType::UserBlocked,
                 "head_ref_deleted" => IssueEventType::HeadRefDeleted,
                 "head_ref_restored" => IssueEventType::HeadRefRestored,
+                "head_ref_force_pushed" => IssueEventType::HeadRefForcePushed,
+                "base_ref_changed" => IssueEventType::BaseRefChanged,
+                "review_requested" => IssueEventType::ReviewRequested,
+                "review_request_removed" => IssueEventType::ReviewRequestRemoved,
+                "review_dismissed" => IssueEventType::ReviewDismissed,
+                "ready_for_review" => IssueEventType::ReadyForReview,
+                "converted_to_draft" => IssueEventType::ConvertedToDraft,
+                "deployed" => IssueEventType::Deployed,
+                "deployment_environment_changed" => IssueEventType::DeploymentEnvironmentChanged,
                 unknown => IssueEventType::Unknown(unknown.to_string()),
             }),
             Err(err) => Err(err),
@@ -165,23 +321,95 @@ impl Decodable for IssueEventType {
     }
 }
 
-/// `EventResponse` represents the response for
-/// all issue event requests found in `activity::events`.
+/// `Milestone` contains the info on the milestone an issue was (de)milestoned for.
+#[derive(Debug, RustcDecodable)]
+pub struct Milestone {
+    pub title: String,
+}
+
+/// `Rename` contains the old and new title of a renamed issue.
 #[derive(Debug, RustcDecodable)]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+}
+
+/// `IssueEventResponse` represents the response for
+/// all issue event requests found in `activity::events`.
+/// Most of the fields are only given for specific types of events.
+#[derive(Debug)]
 pub struct IssueEventResponse {
-    pub public: bool,
-    pub repo: Repository,
-    pub actor: Actor,
-    pub org: Option<Organisation>,
+    pub id: u64,
+    pub url: String,
+    /// the user that generated the event,
+    /// which is `None` in case that user was deleted
+    pub actor: Option<Actor>,
     pub event: IssueEventType,
-    pub created_at: String,
-    pub commit_id: String,
-    pub id: String,
+    /// the commit that referenced, closed or merged the issue
+    pub commit_id: Option<String>,
+    pub commit_url: Option<String>,
+    pub created_at: DateTime<UTC>,
+    /// the issue the event is about, which isn't given
+    /// when listing the events of a single issue
+    pub issue: Option<Issue>,
+    /// given for `Labeled` and `Unlabeled` events
+    pub label: Option<Label>,
+    /// given for `Assigned` and `Unassigned` events
+    pub assignee: Option<User>,
+    /// given for `Assigned` and `Unassigned` events
+    pub assigner: Option<User>,
+    /// given for `ReviewRequested` and `ReviewRequestRemoved` events
+    pub review_requester: Option<User>,
+    /// given for `ReviewRequested` and `ReviewRequestRemoved` events
+    pub requested_reviewer: Option<User>,
+    /// given for `Milestoned` and `Demilestoned` events
+    pub milestone: Option<Milestone>,
+    /// given for `Renamed` events
+    pub rename: Option<Rename>,
+    /// given for `Locked` events, e.g. `off-topic` or `spam`
+    pub lock_reason: Option<String>,
+}
+
+/// Allowing `IssueEventResponse` to be decoded from json values,
+/// decoding the `created_at` key as a timestamp.
+impl Decodable for IssueEventResponse {
+    fn decode<D: Decoder>(d: &mut D) -> Result<IssueEventResponse, D::Error> {
+        d.read_struct("IssueEventResponse", 16, |d| {
+            Ok(IssueEventResponse {
+                id: try!(d.read_struct_field("id", 0, Decodable::decode)),
+                url: try!(d.read_struct_field("url", 1, Decodable::decode)),
+                actor: try!(d.read_struct_field("actor", 2, Decodable::decode)),
+                event: try!(d.read_struct_field("event", 3, Decodable::decode)),
+                commit_id: try!(d.read_struct_field("commit_id", 4, Decodable::decode)),
+                commit_url: try!(d.read_struct_field("commit_url", 5, Decodable::decode)),
+                created_at: try!(d.read_struct_field("created_at", 6, decode_datetime)),
+                issue: try!(d.read_struct_field("issue", 7, Decodable::decode)),
+                label: try!(d.read_struct_field("label", 8, Decodable::decode)),
+                assignee: try!(d.read_struct_field("assignee", 9, Decodable::decode)),
+                assigner: try!(d.read_struct_field("assigner", 10, Decodable::decode)),
+                review_requester: try!(d.read_struct_field("review_requester", 11, Decodable::decode)),
+                requested_reviewer: try!(d.read_struct_field("requested_reviewer", 12, Decodable::decode)),
+                milestone: try!(d.read_struct_field("milestone", 13, Decodable::decode)),
+                rename: try!(d.read_struct_field("rename", 14, Decodable::decode)),
+                lock_reason: try!(d.read_struct_field("lock_reason", 15, Decodable::decode)),
+            })
+        })
+    }
 }
 
 /// `EventReturnType` is the return type for most event-requests.
 pub type EventReturnType = Result<(Vec<EventResponse>, Response), ClientError>;
-/// `EventReturnType` is the return type for issue-event-requests.
+/// `IssueEventReturnType` is the return type for issue-event-requests.
 pub type IssueEventReturnType = Result<(Vec<IssueEventResponse>, Response), ClientError>;
+/// `SingleIssueEventReturnType` is the return type for the request of a single issue event.
+pub type SingleIssueEventReturnType = Result<(IssueEventResponse, Response), ClientError>;
 
 pub mod events;
+pub mod feeds;
+pub mod notifications;
+pub mod payloads;
+pub mod poller;
+pub mod starring;
+pub mod watching;
+
+pub use self::payloads::EventKind;
@@ -0,0 +1,363 @@
+use Client;
+
+use activity::Repository;
+
+use error::ClientError;
+use http::Method;
+use response::{decode_datetime, decode_optional_datetime, Response};
+
+use std::fmt;
+use std::collections::HashMap;
+
+use chrono::{DateTime, UTC};
+
+use rustc_serialize::Decoder;
+use rustc_serialize::Decodable;
+
+/// Documentation References:
+/// https://developer.github.com/v3/activity/notifications/
+
+/// The format of the timestamps given as parameters to the Notifications API.
+static TIMESTAMP_FORMAT: &'static str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// `NotificationReason` is an enumeration of
+/// all the reasons why a user receives a notification.
+#[derive(Debug, PartialEq)]
+pub enum NotificationReason {
+    /// The user was assigned to the issue.
+    Assign,
+    /// The user created the thread.
+    Author,
+    /// The user commented on the thread.
+    Comment,
+    /// The user accepted an invitation to contribute to the repository.
+    Invitation,
+    /// The user subscribed to the thread (via an issue or pull request).
+    Manual,
+    /// The user was specifically @mentioned in the content.
+    Mention,
+    /// The user, or a team the user is a member of, was requested to review a pull request.
+    ReviewRequested,
+    /// GitHub discovered a security vulnerability in the repository.
+    SecurityAlert,
+    /// The user changed the thread state, e.g. by closing an issue.
+    StateChange,
+    /// The user is watching the repository.
+    Subscribed,
+    /// The user is on a team that was mentioned.
+    TeamMention,
+    /// `Unknown(String)` is used as a last resort when a reason is unknown.
+    /// This should never happen, please report/resolve the issue when it does happen.
+    Unknown(String),
+}
+
+/// Allowing `NotificationReason` to be printed via `{}`.
+impl fmt::Display for NotificationReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg: &str = match *self {
+            NotificationReason::Assign => "assigned",
+            NotificationReason::Author => "author",
+            NotificationReason::Comment => "commented",
+            NotificationReason::Invitation => "invitation",
+            NotificationReason::Manual => "subscribed manually",
+            NotificationReason::Mention => "mentioned",
+            NotificationReason::ReviewRequested => "review requested",
+            NotificationReason::SecurityAlert => "security alert",
+            NotificationReason::StateChange => "state changed",
+            NotificationReason::Subscribed => "watching",
+            NotificationReason::TeamMention => "team mentioned",
+            NotificationReason::Unknown(ref s) => &s,
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+/// Allowing `NotificationReason` to be decoded from json values.
+/// Linked to the `reason` key of a `Notification`.
+impl Decodable for NotificationReason {
+    fn decode<D: Decoder>(d: &mut D) -> Result<NotificationReason, D::Error> {
+        match d.read_str() {
+            Ok(reason) => Ok(match &*reason {
+                "assign" => NotificationReason::Assign,
+                "author" => NotificationReason::Author,
+                "comment" => NotificationReason::Comment,
+                "invitation" => NotificationReason::Invitation,
+                "manual" => NotificationReason::Manual,
+                "mention" => NotificationReason::Mention,
+                "review_requested" => NotificationReason::ReviewRequested,
+                "security_alert" => NotificationReason::SecurityAlert,
+                "state_change" => NotificationReason::StateChange,
+                "subscribed" => NotificationReason::Subscribed,
+                "team_mention" => NotificationReason::TeamMention,
+                unknown => NotificationReason::Unknown(unknown.to_string()),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// `NotificationSubject` contains the info on what a notification is about.
+#[derive(Debug)]
+pub struct NotificationSubject {
+    pub title: String,
+    /// the api url of the subject, e.g. of an issue or pull request
+    pub url: Option<String>,
+    pub latest_comment_url: Option<String>,
+    /// the type of the subject, given under the `type` key,
+    /// e.g. `Issue`, `PullRequest`, `Commit` or `Release`
+    pub kind: String,
+}
+
+/// Allowing `NotificationSubject` to be decoded from json values,
+/// reading the `type` key, which is a reserved keyword in Rust.
+impl Decodable for NotificationSubject {
+    fn decode<D: Decoder>(d: &mut D) -> Result<NotificationSubject, D::Error> {
+        d.read_struct("NotificationSubject", 4, |d| {
+            Ok(NotificationSubject {
+                title: try!(d.read_struct_field("title", 0, Decodable::decode)),
+                url: try!(d.read_struct_field("url", 1, Decodable::decode)),
+                latest_comment_url: try!(d.read_struct_field("latest_comment_url", 2, Decodable::decode)),
+                kind: try!(d.read_struct_field("type", 3, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `Notification` represents a notification thread,
+/// which groups the notifications on a single subject.
+#[derive(Debug)]
+pub struct Notification {
+    pub id: String,
+    pub repository: Repository,
+    pub subject: NotificationSubject,
+    pub reason: NotificationReason,
+    pub unread: bool,
+    pub updated_at: DateTime<UTC>,
+    pub last_read_at: Option<DateTime<UTC>>,
+    pub url: String,
+}
+
+/// Allowing `Notification` to be decoded from json values,
+/// decoding the `updated_at` and `last_read_at` keys as timestamps.
+impl Decodable for Notification {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Notification, D::Error> {
+        d.read_struct("Notification", 8, |d| {
+            Ok(Notification {
+                id: try!(d.read_struct_field("id", 0, Decodable::decode)),
+                repository: try!(d.read_struct_field("repository", 1, Decodable::decode)),
+                subject: try!(d.read_struct_field("subject", 2, Decodable::decode)),
+                reason: try!(d.read_struct_field("reason", 3, Decodable::decode)),
+                unread: try!(d.read_struct_field("unread", 4, Decodable::decode)),
+                updated_at: try!(d.read_struct_field("updated_at", 5, decode_datetime)),
+                last_read_at: try!(d.read_struct_field("last_read_at", 6, decode_optional_datetime)),
+                url: try!(d.read_struct_field("url", 7, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `ThreadSubscription` represents the subscription of the user to a notification thread.
+#[derive(Debug)]
+pub struct ThreadSubscription {
+    pub subscribed: bool,
+    /// true when all notifications of the thread are blocked
+    pub ignored: bool,
+    pub reason: Option<String>,
+    pub created_at: Option<DateTime<UTC>>,
+    pub url: String,
+    pub thread_url: String,
+}
+
+/// Allowing `ThreadSubscription` to be decoded from json values,
+/// decoding the `created_at` key as a timestamp.
+impl Decodable for ThreadSubscription {
+    fn decode<D: Decoder>(d: &mut D) -> Result<ThreadSubscription, D::Error> {
+        d.read_struct("ThreadSubscription", 6, |d| {
+            Ok(ThreadSubscription {
+                subscribed: try!(d.read_struct_field("subscribed", 0, Decodable::decode)),
+                ignored: try!(d.read_struct_field("ignored", 1, Decodable::decode)),
+                reason: try!(d.read_struct_field("reason", 2, Decodable::decode)),
+                created_at: try!(d.read_struct_field("created_at", 3, decode_optional_datetime)),
+                url: try!(d.read_struct_field("url", 4, Decodable::decode)),
+                thread_url: try!(d.read_struct_field("thread_url", 5, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `ThreadSubscriptionRequest` is the body sent to set a thread subscription.
+#[derive(RustcEncodable)]
+struct ThreadSubscriptionRequest {
+    ignored: bool,
+}
+
+/// `NotificationFilter` defines which notifications are listed.
+/// By default only the unread notifications of all threads are listed.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationFilter {
+    /// list the notifications that were marked as read as well
+    pub all: bool,
+    /// only list the notifications in which the user is directly participating or mentioned
+    pub participating: bool,
+    /// only list the notifications updated after this moment
+    pub since: Option<DateTime<UTC>>,
+    /// only list the notifications updated before this moment
+    pub before: Option<DateTime<UTC>>,
+}
+
+impl NotificationFilter {
+    /// Construct the default `NotificationFilter`, listing all unread notifications.
+    pub fn new() -> NotificationFilter {
+        NotificationFilter::default()
+    }
+
+    /// List the notifications that were marked as read as well.
+    pub fn all(mut self) -> NotificationFilter {
+        self.all = true;
+        self
+    }
+
+    /// Only list the notifications in which the user is directly participating or mentioned.
+    pub fn participating(mut self) -> NotificationFilter {
+        self.participating = true;
+        self
+    }
+
+    /// Only list the notifications updated after the given moment.
+    pub fn since(mut self, since: DateTime<UTC>) -> NotificationFilter {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only list the notifications updated before the given moment.
+    pub fn before(mut self, before: DateTime<UTC>) -> NotificationFilter {
+        self.before = Some(before);
+        self
+    }
+
+    /// The query of this filter, including the leading `?`, or empty
+    /// when the default filter is used.
+    fn query(&self) -> String {
+        let mut params = Vec::new();
+        if self.all {
+            params.push("all=true".to_string());
+        }
+        if self.participating {
+            params.push("participating=true".to_string());
+        }
+        if let Some(since) = self.since {
+            params.push(format!("since={}", since.format(TIMESTAMP_FORMAT)));
+        }
+        if let Some(before) = self.before {
+            params.push(format!("before={}", before.format(TIMESTAMP_FORMAT)));
+        }
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", params.join("&"))
+        }
+    }
+}
+
+/// `NotificationsReturnType` is the return type for the requests listing notifications.
+pub type NotificationsReturnType = Result<(Vec<Notification>, Response), ClientError>;
+/// `NotificationReturnType` is the return type for the request of a single notification thread.
+pub type NotificationReturnType = Result<(Notification, Response), ClientError>;
+/// `ThreadSubscriptionReturnType` is the return type for thread-subscription-requests.
+pub type ThreadSubscriptionReturnType = Result<(ThreadSubscription, Response), ClientError>;
+
+/// Mark the notifications at the given url as read, up to `last_read_at`,
+/// which is the current time when not given.
+fn mark_as_read(client: &Client, url: &str, last_read_at: Option<DateTime<UTC>>) -> Result<Response, ClientError> {
+    let mut body = HashMap::new();
+    if let Some(last_read_at) = last_read_at {
+        body.insert("last_read_at", last_read_at.format(TIMESTAMP_FORMAT).to_string());
+    }
+    // A `205 Reset Content` is given without a body, while the body of a
+    // `202 Accepted` tells that the request is still processed, in a format
+    // that isn't documented, so the body is discarded.
+    let (_, resp) = try!(::http::request_raw(client, Method::Put, url, None, Some(&body)));
+    Ok(resp)
+}
+
+/// List all notifications for the authenticated user, using the given filter.
+pub fn list_notifications(client: &Client, filter: &NotificationFilter) -> NotificationsReturnType {
+    ::http::get(
+        client,
+        &format!("{}notifications{}", client.base_url, filter.query()),
+        None)
+}
+
+/// List all notifications for the authenticated user in a repository, using the given filter.
+pub fn list_repo_notifications(client: &Client, user: &str, repo: &str, filter: &NotificationFilter) -> NotificationsReturnType {
+    ::http::get(
+        client,
+        &format!("{}repos/{}/{}/notifications{}", client.base_url, user, repo, filter.query()),
+        None)
+}
+
+/// List all notifications for the authenticated user in a repository
+/// owned by the user defined in `Client` as `user_agent`.
+pub fn list_my_repo_notifications(client: &Client, repo: &str, filter: &NotificationFilter) -> NotificationsReturnType {
+    list_repo_notifications(client, &client.user_agent, repo, filter)
+}
+
+/// Mark all notifications of the authenticated user as read,
+/// up to `last_read_at`, which is the current time when not given.
+///
+/// In case there are too many notifications to mark as read at once,
+/// GitHub does so in the background, after a `202 Accepted` response.
+pub fn mark_notifications_as_read(client: &Client, last_read_at: Option<DateTime<UTC>>) -> Result<Response, ClientError> {
+    mark_as_read(client, &format!("{}notifications", client.base_url), last_read_at)
+}
+
+/// Mark all notifications of the authenticated user in a repository as read,
+/// up to `last_read_at`, which is the current time when not given.
+pub fn mark_repo_notifications_as_read(client: &Client, user: &str, repo: &str, last_read_at: Option<DateTime<UTC>>) -> Result<Response, ClientError> {
+    mark_as_read(client, &format!("{}repos/{}/{}/notifications", client.base_url, user, repo), last_read_at)
+}
+
+/// Get a single notification thread by its id.
+pub fn get_thread(client: &Client, id: &str) -> NotificationReturnType {
+    ::http::get_one(
+        client,
+        &format!("{}notifications/threads/{}", client.base_url, id),
+        None)
+}
+
+/// Mark a single notification thread as read.
+pub fn mark_thread_as_read(client: &Client, id: &str) -> Result<Response, ClientError> {
+    ::http::request::<(), ()>(
+        client,
+        Method::Patch,
+        &format!("{}notifications/threads/{}", client.base_url, id),
+        None,
+        None).map(|(_, resp)| resp)
+}
+
+/// Get the subscription of the authenticated user to a notification thread,
+/// which only exists in case the user subscribed to the thread explicitly.
+pub fn get_thread_subscription(client: &Client, id: &str) -> ThreadSubscriptionReturnType {
+    ::http::get_one(
+        client,
+        &format!("{}notifications/threads/{}/subscription", client.base_url, id),
+        None)
+}
+
+/// Subscribe the authenticated user to a notification thread,
+/// or block all of its notifications in case `ignored` is true.
+pub fn set_thread_subscription(client: &Client, id: &str, ignored: bool) -> ThreadSubscriptionReturnType {
+    ::http::put(
+        client,
+        &format!("{}notifications/threads/{}/subscription", client.base_url, id),
+        &ThreadSubscriptionRequest { ignored: ignored })
+}
+
+/// Delete the subscription of the authenticated user to a notification thread,
+/// after which the user only receives notifications when participating in the thread.
+pub fn delete_thread_subscription(client: &Client, id: &str) -> Result<Response, ClientError> {
+    ::http::delete(
+        client,
+        &format!("{}notifications/threads/{}/subscription", client.base_url, id))
+}
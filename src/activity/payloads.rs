@@ -0,0 +1,605 @@
+use activity::Repository;
+
+use response::{decode_datetime, decode_optional_datetime};
+
+use chrono::{DateTime, UTC};
+
+use rustc_serialize::Decoder;
+use rustc_serialize::Decodable;
+use rustc_serialize::json;
+use rustc_serialize::json::{DecoderError, Json};
+
+/// Documentation References:
+/// https://developer.github.com/v3/activity/event_types/
+
+/// The payload of an event depends on its type, as given by `EventKind`.
+/// The structs below represent the objects found in these payloads,
+/// which are abbreviated versions of the objects as given by their own API.
+
+/// `User` contains the info on a user found in an event payload.
+#[derive(Debug, RustcDecodable)]
+pub struct User {
+    pub id: u64,
+    pub login: String,
+    pub avatar_url: Option<String>,
+    pub url: Option<String>,
+    pub html_url: Option<String>,
+    pub site_admin: Option<bool>,
+}
+
+/// `Label` contains the info on a label of an issue or pull request.
+#[derive(Debug, RustcDecodable)]
+pub struct Label {
+    pub id: Option<u64>,
+    pub name: String,
+    pub color: Option<String>,
+    pub url: Option<String>,
+}
+
+/// `Issue` contains the info on the issue an event is about.
+#[derive(Debug)]
+pub struct Issue {
+    pub id: u64,
+    pub number: u64,
+    pub title: String,
+    pub user: User,
+    pub state: String,
+    pub body: Option<String>,
+    pub labels: Option<Vec<Label>>,
+    pub assignee: Option<User>,
+    pub comments: Option<u64>,
+    pub url: String,
+    pub html_url: String,
+    pub created_at: DateTime<UTC>,
+    pub updated_at: Option<DateTime<UTC>>,
+    pub closed_at: Option<DateTime<UTC>>,
+}
+
+/// Allowing `Issue` to be decoded from json values,
+/// decoding the `created_at`, `updated_at` and `closed_at` keys as timestamps.
+impl Decodable for Issue {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Issue, D::Error> {
+        d.read_struct("Issue", 14, |d| {
+            Ok(Issue {
+                id: try!(d.read_struct_field("id", 0, Decodable::decode)),
+                number: try!(d.read_struct_field("number", 1, Decodable::decode)),
+                title: try!(d.read_struct_field("title", 2, Decodable::decode)),
+                user: try!(d.read_struct_field("user", 3, Decodable::decode)),
+                state: try!(d.read_struct_field("state", 4, Decodable::decode)),
+                body: try!(d.read_struct_field("body", 5, Decodable::decode)),
+                labels: try!(d.read_struct_field("labels", 6, Decodable::decode)),
+                assignee: try!(d.read_struct_field("assignee", 7, Decodable::decode)),
+                comments: try!(d.read_struct_field("comments", 8, Decodable::decode)),
+                url: try!(d.read_struct_field("url", 9, Decodable::decode)),
+                html_url: try!(d.read_struct_field("html_url", 10, Decodable::decode)),
+                created_at: try!(d.read_struct_field("created_at", 11, decode_datetime)),
+                updated_at: try!(d.read_struct_field("updated_at", 12, decode_optional_datetime)),
+                closed_at: try!(d.read_struct_field("closed_at", 13, decode_optional_datetime)),
+            })
+        })
+    }
+}
+
+/// `Comment` contains the info on a comment made on an issue,
+/// a commit or (the diff of) a pull request.
+#[derive(Debug)]
+pub struct Comment {
+    pub id: u64,
+    pub body: String,
+    pub user: User,
+    pub url: String,
+    pub html_url: String,
+    pub created_at: DateTime<UTC>,
+    pub updated_at: Option<DateTime<UTC>>,
+    /// the commented commit, for commit and review comments
+    pub commit_id: Option<String>,
+    /// the commented file, for commit and review comments on a line
+    pub path: Option<String>,
+    /// the commented line of the diff, for commit and review comments on a line
+    pub position: Option<u64>,
+}
+
+/// Allowing `Comment` to be decoded from json values,
+/// decoding the `created_at` and `updated_at` keys as timestamps.
+impl Decodable for Comment {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Comment, D::Error> {
+        d.read_struct("Comment", 10, |d| {
+            Ok(Comment {
+                id: try!(d.read_struct_field("id", 0, Decodable::decode)),
+                body: try!(d.read_struct_field("body", 1, Decodable::decode)),
+                user: try!(d.read_struct_field("user", 2, Decodable::decode)),
+                url: try!(d.read_struct_field("url", 3, Decodable::decode)),
+                html_url: try!(d.read_struct_field("html_url", 4, Decodable::decode)),
+                created_at: try!(d.read_struct_field("created_at", 5, decode_datetime)),
+                updated_at: try!(d.read_struct_field("updated_at", 6, decode_optional_datetime)),
+                commit_id: try!(d.read_struct_field("commit_id", 7, Decodable::decode)),
+                path: try!(d.read_struct_field("path", 8, Decodable::decode)),
+                position: try!(d.read_struct_field("position", 9, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `PullRequestRef` contains the info on the head or base of a pull request.
+#[derive(Debug)]
+pub struct PullRequestRef {
+    pub label: String,
+    /// the name of the branch, given under the `ref` key
+    pub git_ref: String,
+    pub sha: String,
+    pub repo: Option<Repository>,
+}
+
+/// Allowing `PullRequestRef` to be decoded from json values,
+/// reading the `ref` key, which is a reserved keyword in Rust.
+impl Decodable for PullRequestRef {
+    fn decode<D: Decoder>(d: &mut D) -> Result<PullRequestRef, D::Error> {
+        d.read_struct("PullRequestRef", 4, |d| {
+            Ok(PullRequestRef {
+                label: try!(d.read_struct_field("label", 0, Decodable::decode)),
+                git_ref: try!(d.read_struct_field("ref", 1, Decodable::decode)),
+                sha: try!(d.read_struct_field("sha", 2, Decodable::decode)),
+                repo: try!(d.read_struct_field("repo", 3, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `PullRequest` contains the info on the pull request an event is about.
+#[derive(Debug)]
+pub struct PullRequest {
+    pub id: u64,
+    pub number: u64,
+    pub title: String,
+    pub user: User,
+    pub state: String,
+    pub body: Option<String>,
+    pub head: PullRequestRef,
+    pub base: PullRequestRef,
+    pub merged: Option<bool>,
+    pub url: String,
+    pub html_url: String,
+    pub created_at: DateTime<UTC>,
+    pub updated_at: Option<DateTime<UTC>>,
+    pub closed_at: Option<DateTime<UTC>>,
+    pub merged_at: Option<DateTime<UTC>>,
+}
+
+/// Allowing `PullRequest` to be decoded from json values,
+/// decoding the `created_at`, `updated_at`, `closed_at` and `merged_at` keys as timestamps.
+impl Decodable for PullRequest {
+    fn decode<D: Decoder>(d: &mut D) -> Result<PullRequest, D::Error> {
+        d.read_struct("PullRequest", 15, |d| {
+            Ok(PullRequest {
+                id: try!(d.read_struct_field("id", 0, Decodable::decode)),
+                number: try!(d.read_struct_field("number", 1, Decodable::decode)),
+                title: try!(d.read_struct_field("title", 2, Decodable::decode)),
+                user: try!(d.read_struct_field("user", 3, Decodable::decode)),
+                state: try!(d.read_struct_field("state", 4, Decodable::decode)),
+                body: try!(d.read_struct_field("body", 5, Decodable::decode)),
+                head: try!(d.read_struct_field("head", 6, Decodable::decode)),
+                base: try!(d.read_struct_field("base", 7, Decodable::decode)),
+                merged: try!(d.read_struct_field("merged", 8, Decodable::decode)),
+                url: try!(d.read_struct_field("url", 9, Decodable::decode)),
+                html_url: try!(d.read_struct_field("html_url", 10, Decodable::decode)),
+                created_at: try!(d.read_struct_field("created_at", 11, decode_datetime)),
+                updated_at: try!(d.read_struct_field("updated_at", 12, decode_optional_datetime)),
+                closed_at: try!(d.read_struct_field("closed_at", 13, decode_optional_datetime)),
+                merged_at: try!(d.read_struct_field("merged_at", 14, decode_optional_datetime)),
+            })
+        })
+    }
+}
+
+/// `Review` contains the info on a review of a pull request.
+#[derive(Debug)]
+pub struct Review {
+    pub id: u64,
+    pub user: User,
+    pub body: Option<String>,
+    /// e.g. `approved`, `changes_requested` or `commented`
+    pub state: String,
+    pub commit_id: Option<String>,
+    pub html_url: String,
+    pub submitted_at: Option<DateTime<UTC>>,
+}
+
+/// Allowing `Review` to be decoded from json values,
+/// decoding the `submitted_at` key as a timestamp.
+impl Decodable for Review {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Review, D::Error> {
+        d.read_struct("Review", 7, |d| {
+            Ok(Review {
+                id: try!(d.read_struct_field("id", 0, Decodable::decode)),
+                user: try!(d.read_struct_field("user", 1, Decodable::decode)),
+                body: try!(d.read_struct_field("body", 2, Decodable::decode)),
+                state: try!(d.read_struct_field("state", 3, Decodable::decode)),
+                commit_id: try!(d.read_struct_field("commit_id", 4, Decodable::decode)),
+                html_url: try!(d.read_struct_field("html_url", 5, Decodable::decode)),
+                submitted_at: try!(d.read_struct_field("submitted_at", 6, decode_optional_datetime)),
+            })
+        })
+    }
+}
+
+/// `ReviewThread` contains the info on a thread of review comments on a pull request.
+#[derive(Debug, RustcDecodable)]
+pub struct ReviewThread {
+    pub node_id: String,
+    pub comments: Vec<Comment>,
+}
+
+/// `Release` contains the info on the release an event is about.
+#[derive(Debug)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    pub target_commitish: Option<String>,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub author: User,
+    pub url: String,
+    pub html_url: String,
+    pub created_at: DateTime<UTC>,
+    pub published_at: Option<DateTime<UTC>>,
+}
+
+/// Allowing `Release` to be decoded from json values,
+/// decoding the `created_at` and `published_at` keys as timestamps.
+impl Decodable for Release {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Release, D::Error> {
+        d.read_struct("Release", 12, |d| {
+            Ok(Release {
+                id: try!(d.read_struct_field("id", 0, Decodable::decode)),
+                tag_name: try!(d.read_struct_field("tag_name", 1, Decodable::decode)),
+                target_commitish: try!(d.read_struct_field("target_commitish", 2, Decodable::decode)),
+                name: try!(d.read_struct_field("name", 3, Decodable::decode)),
+                body: try!(d.read_struct_field("body", 4, Decodable::decode)),
+                draft: try!(d.read_struct_field("draft", 5, Decodable::decode)),
+                prerelease: try!(d.read_struct_field("prerelease", 6, Decodable::decode)),
+                author: try!(d.read_struct_field("author", 7, Decodable::decode)),
+                url: try!(d.read_struct_field("url", 8, Decodable::decode)),
+                html_url: try!(d.read_struct_field("html_url", 9, Decodable::decode)),
+                created_at: try!(d.read_struct_field("created_at", 10, decode_datetime)),
+                published_at: try!(d.read_struct_field("published_at", 11, decode_optional_datetime)),
+            })
+        })
+    }
+}
+
+/// `WikiPage` contains the info on a wiki page that was created or updated.
+#[derive(Debug, RustcDecodable)]
+pub struct WikiPage {
+    pub page_name: String,
+    pub title: String,
+    pub summary: Option<String>,
+    /// either `created` or `edited`
+    pub action: String,
+    pub sha: String,
+    pub html_url: String,
+}
+
+/// `CommitAuthor` contains the git author info of a commit.
+#[derive(Debug, RustcDecodable)]
+pub struct CommitAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+/// `Commit` contains the info on a commit that was pushed.
+#[derive(Debug, RustcDecodable)]
+pub struct Commit {
+    pub sha: String,
+    pub message: String,
+    pub author: CommitAuthor,
+    /// whether this commit is distinct from any that have been pushed before
+    pub distinct: bool,
+    pub url: String,
+}
+
+/// The payload of a `CommitCommentEvent`, triggered when a commit comment is created.
+#[derive(Debug, RustcDecodable)]
+pub struct CommitCommentEventPayload {
+    pub action: Option<String>,
+    pub comment: Comment,
+}
+
+/// The payload of a `CreateEvent`, triggered when a repository, branch or tag is created.
+#[derive(Debug)]
+pub struct CreateEventPayload {
+    /// the name of the branch or tag, given under the `ref` key,
+    /// which is `None` when a repository was created
+    pub git_ref: Option<String>,
+    /// either `repository`, `branch` or `tag`
+    pub ref_type: String,
+    pub master_branch: String,
+    pub description: Option<String>,
+    pub pusher_type: Option<String>,
+}
+
+/// Allowing `CreateEventPayload` to be decoded from json values,
+/// reading the `ref` key, which is a reserved keyword in Rust.
+impl Decodable for CreateEventPayload {
+    fn decode<D: Decoder>(d: &mut D) -> Result<CreateEventPayload, D::Error> {
+        d.read_struct("CreateEventPayload", 5, |d| {
+            Ok(CreateEventPayload {
+                git_ref: try!(d.read_struct_field("ref", 0, Decodable::decode)),
+                ref_type: try!(d.read_struct_field("ref_type", 1, Decodable::decode)),
+                master_branch: try!(d.read_struct_field("master_branch", 2, Decodable::decode)),
+                description: try!(d.read_struct_field("description", 3, Decodable::decode)),
+                pusher_type: try!(d.read_struct_field("pusher_type", 4, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// The payload of a `DeleteEvent`, triggered when a branch or tag is deleted.
+#[derive(Debug)]
+pub struct DeleteEventPayload {
+    /// the name of the branch or tag, given under the `ref` key
+    pub git_ref: String,
+    /// either `branch` or `tag`
+    pub ref_type: String,
+    pub pusher_type: Option<String>,
+}
+
+/// Allowing `DeleteEventPayload` to be decoded from json values,
+/// reading the `ref` key, which is a reserved keyword in Rust.
+impl Decodable for DeleteEventPayload {
+    fn decode<D: Decoder>(d: &mut D) -> Result<DeleteEventPayload, D::Error> {
+        d.read_struct("DeleteEventPayload", 3, |d| {
+            Ok(DeleteEventPayload {
+                git_ref: try!(d.read_struct_field("ref", 0, Decodable::decode)),
+                ref_type: try!(d.read_struct_field("ref_type", 1, Decodable::decode)),
+                pusher_type: try!(d.read_struct_field("pusher_type", 2, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// The payload of a `ForkEvent`, triggered when a user forks a repository.
+#[derive(Debug, RustcDecodable)]
+pub struct ForkEventPayload {
+    /// the created repository
+    pub forkee: Repository,
+}
+
+/// The payload of a `GollumEvent`, triggered when a wiki page is created or updated.
+#[derive(Debug, RustcDecodable)]
+pub struct GollumEventPayload {
+    pub pages: Vec<WikiPage>,
+}
+
+/// The payload of an `IssueCommentEvent`, triggered
+/// when an issue (or pull request) comment is created, edited or deleted.
+#[derive(Debug, RustcDecodable)]
+pub struct IssueCommentEventPayload {
+    /// either `created`, `edited` or `deleted`
+    pub action: String,
+    pub issue: Issue,
+    pub comment: Comment,
+}
+
+/// The payload of an `IssuesEvent`, triggered when an issue is opened, edited,
+/// closed, reopened, (un)assigned or (un)labeled, among others.
+#[derive(Debug, RustcDecodable)]
+pub struct IssuesEventPayload {
+    /// e.g. `opened`, `closed`, `reopened`, `assigned` or `labeled`
+    pub action: String,
+    pub issue: Issue,
+    /// the (un)assigned user, if the action was `assigned` or `unassigned`
+    pub assignee: Option<User>,
+    /// the (un)applied label, if the action was `labeled` or `unlabeled`
+    pub label: Option<Label>,
+}
+
+/// The payload of a `MemberEvent`, triggered
+/// when a user is added as a collaborator to a repository.
+#[derive(Debug, RustcDecodable)]
+pub struct MemberEventPayload {
+    /// e.g. `added`
+    pub action: String,
+    pub member: User,
+}
+
+/// The payload of a `PullRequestEvent`, triggered when a pull request is opened, edited,
+/// closed, reopened, (un)assigned or (un)labeled, among others.
+#[derive(Debug, RustcDecodable)]
+pub struct PullRequestEventPayload {
+    /// e.g. `opened`, `closed`, `reopened`, `assigned` or `review_requested`
+    pub action: String,
+    pub number: u64,
+    pub pull_request: PullRequest,
+}
+
+/// The payload of a `PullRequestReviewEvent`, triggered when a pull request review is submitted.
+#[derive(Debug, RustcDecodable)]
+pub struct PullRequestReviewEventPayload {
+    /// e.g. `created`
+    pub action: String,
+    pub review: Review,
+    pub pull_request: PullRequest,
+}
+
+/// The payload of a `PullRequestReviewCommentEvent`, triggered
+/// when a comment on the diff of a pull request is created, edited or deleted.
+#[derive(Debug, RustcDecodable)]
+pub struct PullRequestReviewCommentEventPayload {
+    /// either `created`, `edited` or `deleted`
+    pub action: String,
+    pub comment: Comment,
+    pub pull_request: PullRequest,
+}
+
+/// The payload of a `PullRequestReviewThreadEvent`, triggered
+/// when a thread of review comments on a pull request is resolved or unresolved.
+#[derive(Debug, RustcDecodable)]
+pub struct PullRequestReviewThreadEventPayload {
+    /// either `resolved` or `unresolved`
+    pub action: String,
+    pub pull_request: PullRequest,
+    pub thread: ReviewThread,
+}
+
+/// The payload of a `PushEvent`, triggered when commits are pushed to a branch or tag.
+#[derive(Debug)]
+pub struct PushEventPayload {
+    pub push_id: u64,
+    /// the number of commits in the push, which is left out of some events
+    pub size: Option<u64>,
+    /// the number of distinct commits in the push, which is left out of some events
+    pub distinct_size: Option<u64>,
+    /// the full ref that was pushed, given under the `ref` key, e.g. `refs/heads/master`
+    pub git_ref: String,
+    /// the sha of the most recent commit on the ref after the push
+    pub head: String,
+    /// the sha of the most recent commit on the ref before the push
+    pub before: String,
+    /// the pushed commits, listing at most 20 commits,
+    /// which are left out of some events
+    pub commits: Option<Vec<Commit>>,
+}
+
+/// Allowing `PushEventPayload` to be decoded from json values,
+/// reading the `ref` key, which is a reserved keyword in Rust.
+impl Decodable for PushEventPayload {
+    fn decode<D: Decoder>(d: &mut D) -> Result<PushEventPayload, D::Error> {
+        d.read_struct("PushEventPayload", 7, |d| {
+            Ok(PushEventPayload {
+                push_id: try!(d.read_struct_field("push_id", 0, Decodable::decode)),
+                size: try!(d.read_struct_field("size", 1, Decodable::decode)),
+                distinct_size: try!(d.read_struct_field("distinct_size", 2, Decodable::decode)),
+                git_ref: try!(d.read_struct_field("ref", 3, Decodable::decode)),
+                head: try!(d.read_struct_field("head", 4, Decodable::decode)),
+                before: try!(d.read_struct_field("before", 5, Decodable::decode)),
+                commits: try!(d.read_struct_field("commits", 6, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// The payload of a `ReleaseEvent`, triggered when a release is published.
+#[derive(Debug, RustcDecodable)]
+pub struct ReleaseEventPayload {
+    /// e.g. `published`
+    pub action: String,
+    pub release: Release,
+}
+
+/// The payload of a `SponsorshipEvent`, triggered when a sponsorship listing is changed.
+#[derive(Debug)]
+pub struct SponsorshipEventPayload {
+    /// e.g. `created`
+    pub action: String,
+    pub effective_date: Option<DateTime<UTC>>,
+}
+
+/// Allowing `SponsorshipEventPayload` to be decoded from json values,
+/// decoding the `effective_date` key as a timestamp.
+impl Decodable for SponsorshipEventPayload {
+    fn decode<D: Decoder>(d: &mut D) -> Result<SponsorshipEventPayload, D::Error> {
+        d.read_struct("SponsorshipEventPayload", 2, |d| {
+            Ok(SponsorshipEventPayload {
+                action: try!(d.read_struct_field("action", 0, Decodable::decode)),
+                effective_date: try!(d.read_struct_field("effective_date", 1, decode_optional_datetime)),
+            })
+        })
+    }
+}
+
+/// The payload of a `WatchEvent`, triggered when a user stars a repository.
+#[derive(Debug, RustcDecodable)]
+pub struct WatchEventPayload {
+    /// which is always `started`
+    pub action: String,
+}
+
+/// `EventKind` enumerates all the types of events given by the Events API,
+/// each with its own payload.
+#[derive(Debug)]
+pub enum EventKind {
+    CommitComment(CommitCommentEventPayload),
+    Create(CreateEventPayload),
+    Delete(DeleteEventPayload),
+    Fork(ForkEventPayload),
+    Gollum(GollumEventPayload),
+    IssueComment(IssueCommentEventPayload),
+    Issues(IssuesEventPayload),
+    Member(MemberEventPayload),
+    /// A private repository was made public, which comes without payload.
+    Public,
+    PullRequest(PullRequestEventPayload),
+    PullRequestReview(PullRequestReviewEventPayload),
+    PullRequestReviewComment(PullRequestReviewCommentEventPayload),
+    PullRequestReviewThread(PullRequestReviewThreadEventPayload),
+    Push(PushEventPayload),
+    Release(ReleaseEventPayload),
+    Sponsorship(SponsorshipEventPayload),
+    Watch(WatchEventPayload),
+    /// `Unknown` is used for event types unknown to this library,
+    /// keeping the type and the raw json payload.
+    Unknown(String, Json),
+    /// `Invalid` is used for payloads that couldn't be decoded as the payload
+    /// of their known type, keeping the type, e.g. `PushEvent`,
+    /// the raw json payload and the error that occurred decoding it.
+    Invalid(String, Json, DecoderError),
+}
+
+/// Decode an event payload of a known type.
+fn decode_payload<T: Decodable>(payload: &Json) -> Result<T, DecoderError> {
+    Decodable::decode(&mut json::Decoder::new(payload.clone()))
+}
+
+impl EventKind {
+    /// Construct the `EventKind` of an event with the given type and payload.
+    pub fn new(name: &str, payload: Json) -> EventKind {
+        let kind = match name {
+            "CommitCommentEvent" => decode_payload(&payload).map(EventKind::CommitComment),
+            "CreateEvent" => decode_payload(&payload).map(EventKind::Create),
+            "DeleteEvent" => decode_payload(&payload).map(EventKind::Delete),
+            "ForkEvent" => decode_payload(&payload).map(EventKind::Fork),
+            "GollumEvent" => decode_payload(&payload).map(EventKind::Gollum),
+            "IssueCommentEvent" => decode_payload(&payload).map(EventKind::IssueComment),
+            "IssuesEvent" => decode_payload(&payload).map(EventKind::Issues),
+            "MemberEvent" => decode_payload(&payload).map(EventKind::Member),
+            "PublicEvent" => Ok(EventKind::Public),
+            "PullRequestEvent" => decode_payload(&payload).map(EventKind::PullRequest),
+            "PullRequestReviewEvent" => decode_payload(&payload).map(EventKind::PullRequestReview),
+            "PullRequestReviewCommentEvent" => decode_payload(&payload).map(EventKind::PullRequestReviewComment),
+            "PullRequestReviewThreadEvent" => decode_payload(&payload).map(EventKind::PullRequestReviewThread),
+            "PushEvent" => decode_payload(&payload).map(EventKind::Push),
+            "ReleaseEvent" => decode_payload(&payload).map(EventKind::Release),
+            "SponsorshipEvent" => decode_payload(&payload).map(EventKind::Sponsorship),
+            "WatchEvent" => decode_payload(&payload).map(EventKind::Watch),
+            _ => return EventKind::Unknown(name.to_string(), payload),
+        };
+        kind.unwrap_or_else(|e| EventKind::Invalid(name.to_string(), payload, e))
+    }
+
+    /// The type of the event as given by the Events API, e.g. `PushEvent`.
+    pub fn name(&self) -> &str {
+        match *self {
+            EventKind::CommitComment(_) => "CommitCommentEvent",
+            EventKind::Create(_) => "CreateEvent",
+            EventKind::Delete(_) => "DeleteEvent",
+            EventKind::Fork(_) => "ForkEvent",
+            EventKind::Gollum(_) => "GollumEvent",
+            EventKind::IssueComment(_) => "IssueCommentEvent",
+            EventKind::Issues(_) => "IssuesEvent",
+            EventKind::Member(_) => "MemberEvent",
+            EventKind::Public => "PublicEvent",
+            EventKind::PullRequest(_) => "PullRequestEvent",
+            EventKind::PullRequestReview(_) => "PullRequestReviewEvent",
+            EventKind::PullRequestReviewComment(_) => "PullRequestReviewCommentEvent",
+            EventKind::PullRequestReviewThread(_) => "PullRequestReviewThreadEvent",
+            EventKind::Push(_) => "PushEvent",
+            EventKind::Release(_) => "ReleaseEvent",
+            EventKind::Sponsorship(_) => "SponsorshipEvent",
+            EventKind::Watch(_) => "WatchEvent",
+            EventKind::Unknown(ref name, _) => name,
+            EventKind::Invalid(ref name, _, _) => name,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use Client;
+
+use activity::EventResponse;
+use activity::events;
+
+use error::{ClientError, ErrorStatus};
+
+use response::{Populatable, Response};
+
+use std::thread;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Documentation References:
+/// https://developer.github.com/v3/activity/events/
+
+/// The interval used when no X-Poll-Interval header was given, which is
+/// the interval GitHub asks for, unless it's under a high load.
+const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 60;
+
+/// `EventPoller` is an endless iterator over the new events listed by
+/// an endpoint of the Events API, polling it at the interval advised by GitHub
+/// via the X-Poll-Interval header, which is 60 seconds in general.
+///
+/// It remembers the id of the last event seen, so only new events are yielded,
+/// from the oldest to the most recent one. All events of the first page
+/// are considered new, unless the poller is started `after` a known event.
+/// Only the first page is requested per poll, so when more events happen
+/// between two polls than fit on a page, the oldest of them are missed.
+///
+/// Each poll is a conditional request, sending the ETag of the previous poll
+/// as If-None-Match, whether or not the `Client` has a `Cache`. GitHub answers
+/// it with a `304 Not Modified` when there are no new events, which doesn't
+/// count against the rate limit.
+/// A failed poll is given as an error, after which polling continues.
+///
+/// ```no_run
+/// use github::Client;
+/// use github::activity::poller::EventPoller;
+///
+/// let client = Client::new("glendc");
+/// let poller = EventPoller::for_public_organisation(&client, "rust-lang");
+/// for event in poller {
+///     match event {
+///         Ok(event) => println!("{} by {}", event.kind.name(), event.actor.login),
+///         Err(err) => println!("polling failed: {}", err),
+///     }
+/// }
+/// ```
+pub struct EventPoller<'a> {
+    client: &'a Client,
+    /// the url listing the events, e.g. `https://api.github.com/events`
+    url: String,
+    /// the ETag of the last successful poll, if any
+    etag: Option<String>,
+    /// the interval advised by the last poll, in seconds
+    interval: u64,
+    /// the id of the most recent event seen, if any
+    last_id: Option<u64>,
+    /// the new events that haven't been yielded yet
+    events: VecDeque<EventResponse>,
+    /// the moment of the next poll, which is right away for the first one
+    next_poll: Option<Instant>,
+}
+
+impl<'a> EventPoller<'a> {
+    /// Construct an `EventPoller` listing the events at the given url.
+    fn new(client: &'a Client, url: String) -> EventPoller<'a> {
+        EventPoller {
+            client: client,
+            url: url,
+            etag: None,
+            interval: DEFAULT_POLL_INTERVAL_SECONDS,
+            last_id: None,
+            events: VecDeque::new(),
+            next_poll: None,
+        }
+    }
+
+    /// Poll the public events, as listed by `list_events`.
+    pub fn for_events(client: &'a Client) -> EventPoller<'a> {
+        EventPoller::new(client, events::events_url(client))
+    }
+
+    /// Poll the events of a repository, as listed by `list_repo_events`.
+    pub fn for_repo(client: &'a Client, user: &str, repo: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::repo_events_url(client, user, repo))
+    }
+
+    /// Poll the events that a user has received, as listed by `list_received_user_events`.
+    pub fn for_received_user(client: &'a Client, user: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::received_user_events_url(client, user))
+    }
+
+    /// Poll the public events that a user has received,
+    /// as listed by `list_received_public_user_events`.
+    pub fn for_received_public_user(client: &'a Client, user: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::received_public_user_events_url(client, user))
+    }
+
+    /// Poll the events performed by a user, as listed by `list_user_events`.
+    pub fn for_user(client: &'a Client, user: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::user_events_url(client, user))
+    }
+
+    /// Poll the public events performed by a user, as listed by `list_public_user_events`.
+    pub fn for_public_user(client: &'a Client, user: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::public_user_events_url(client, user))
+    }
+
+    /// Poll the public events for a network of repositories,
+    /// as listed by `list_public_network_repo_events`.
+    pub fn for_public_network_repo(client: &'a Client, user: &str, repo: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::public_network_repo_events_url(client, user, repo))
+    }
+
+    /// Poll the public events for an organization,
+    /// as listed by `list_public_organisation_events`.
+    pub fn for_public_organisation(client: &'a Client, org: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::public_organisation_events_url(client, org))
+    }
+
+    /// Poll the events for an organization, as listed by `list_organisation_events`.
+    pub fn for_organisation(client: &'a Client, user: &str, org: &str) -> EventPoller<'a> {
+        EventPoller::new(client, events::organisation_events_url(client, user, org))
+    }
+
+    /// Only yield the events that happened after the event with the given id,
+    /// e.g. to resume polling where a previous poller stopped.
+    pub fn after(mut self, id: u64) -> EventPoller<'a> {
+        self.last_id = Some(id);
+        self
+    }
+
+    /// The id of the most recent event seen so far, if any.
+    pub fn last_id(&self) -> Option<u64> {
+        self.last_id
+    }
+
+    /// Poll the events once, buffering those that are new,
+    /// and schedule the next poll as advised by the response.
+    fn poll(&mut self) -> Result<(), ClientError> {
+        let result = {
+            let opts = self.etag.as_ref().map(|etag| vec![("If-None-Match", &etag[..])]);
+            ::http::get::<EventResponse>(self.client, &self.url, opts)
+        };
+        // The interval is advised by the X-Poll-Interval header of each response,
+        // including a `304 Not Modified` given as an error.
+        let interval = match result {
+            Ok((_, ref resp)) => Some(resp.poll_interval),
+            Err(ClientError::Http(ref e)) if e.code == ErrorStatus::NotModified =>
+                Some(Response::populate(&e.headers).poll_interval),
+            Err(_) => None,
+        };
+        if let Some(interval) = interval {
+            self.interval = interval.unwrap_or(DEFAULT_POLL_INTERVAL_SECONDS);
+        }
+        self.next_poll = Some(Instant::now() + Duration::from_secs(self.interval));
+
+        let (events, resp) = match result {
+            Ok(result) => result,
+            // Nothing changed since the last poll, which is given as an error
+            // when the `Client` has no cached response to reuse.
+            Err(ClientError::Http(ref e)) if e.code == ErrorStatus::NotModified => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        // A cached response also means that nothing changed since the last poll.
+        if resp.cached {
+            return Ok(());
+        }
+        if let Some(etag) = resp.resp.get("etag").and_then(|values| values.first()) {
+            self.etag = Some(etag.clone());
+        }
+
+        // Events are listed from the most recent to the oldest one.
+        let last_id = self.last_id;
+        let mut new: Vec<EventResponse> = events.into_iter()
+            .filter(|event| match (event.id.parse::<u64>(), last_id) {
+                (Ok(id), Some(last_id)) => id > last_id,
+                _ => true,
+            })
+            .collect();
+        if let Some(id) = new.first().and_then(|event| event.id.parse().ok()) {
+            self.last_id = Some(id);
+        }
+        new.reverse();
+        self.events.extend(new);
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for EventPoller<'a> {
+    type Item = Result<EventResponse, ClientError>;
+
+    fn next(&mut self) -> Option<Result<EventResponse, ClientError>> {
+        while self.events.is_empty() {
+            if let Some(next_poll) = self.next_poll {
+                let now = Instant::now();
+                if next_poll > now {
+                    thread::sleep(next_poll - now);
+                }
+            }
+            if let Err(err) = self.poll() {
+                return Some(Err(err));
+            }
+        }
+        self.events.pop_front().map(Ok)
+    }
+}
@@ -0,0 +1,152 @@
+use Client;
+
+use activity::Repository;
+use activity::payloads::User;
+
+use error::*;
+use http::Method;
+use response::{decode_datetime, Response};
+
+use chrono::{DateTime, UTC};
+
+use rustc_serialize::Decoder;
+use rustc_serialize::Decodable;
+
+/// Documentation References:
+/// https://developer.github.com/v3/activity/starring/
+
+/// The Accept header value used to include the moment a repository
+/// was starred, given under the `starred_at` key.
+static STAR_ACCEPT_HEADER: &'static str = "application/vnd.github.v3.star+json";
+
+/// `Stargazer` is a user that starred a repository,
+/// together with the moment that user starred it.
+#[derive(Debug)]
+pub struct Stargazer {
+    pub starred_at: DateTime<UTC>,
+    pub user: User,
+}
+
+/// Allowing `Stargazer` to be decoded from json values,
+/// decoding the `starred_at` key as a timestamp.
+impl Decodable for Stargazer {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Stargazer, D::Error> {
+        d.read_struct("Stargazer", 2, |d| {
+            Ok(Stargazer {
+                starred_at: try!(d.read_struct_field("starred_at", 0, decode_datetime)),
+                user: try!(d.read_struct_field("user", 1, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `StarredRepository` is a repository starred by a user,
+/// together with the moment that user starred it.
+#[derive(Debug)]
+pub struct StarredRepository {
+    pub starred_at: DateTime<UTC>,
+    pub repo: Repository,
+}
+
+/// Allowing `StarredRepository` to be decoded from json values,
+/// decoding the `starred_at` key as a timestamp.
+impl Decodable for StarredRepository {
+    fn decode<D: Decoder>(d: &mut D) -> Result<StarredRepository, D::Error> {
+        d.read_struct("StarredRepository", 2, |d| {
+            Ok(StarredRepository {
+                starred_at: try!(d.read_struct_field("starred_at", 0, decode_datetime)),
+                repo: try!(d.read_struct_field("repo", 1, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `StargazerReturnType` is the return type for the requests listing stargazers.
+pub type StargazerReturnType = Result<(Vec<User>, Response), ClientError>;
+/// `StargazerWithTimestampReturnType` is the return type for the requests
+/// listing stargazers together with the moment they starred the repository.
+pub type StargazerWithTimestampReturnType = Result<(Vec<Stargazer>, Response), ClientError>;
+/// `StarredReturnType` is the return type for the requests listing starred repositories.
+pub type StarredReturnType = Result<(Vec<Repository>, Response), ClientError>;
+/// `StarredWithTimestampReturnType` is the return type for the requests
+/// listing starred repositories together with the moment they were starred.
+pub type StarredWithTimestampReturnType = Result<(Vec<StarredRepository>, Response), ClientError>;
+
+/// List the users that starred a repository.
+pub fn list_stargazers(client: &Client, user: &str, repo: &str) -> StargazerReturnType {
+    ::http::get(
+        client,
+        &format!("{}repos/{}/{}/stargazers", client.base_url, user, repo),
+        None)
+}
+
+/// List the users that starred a repository,
+/// together with the moment they starred it.
+pub fn list_stargazers_with_timestamps(client: &Client, user: &str, repo: &str) -> StargazerWithTimestampReturnType {
+    ::http::get(
+        client,
+        &format!("{}repos/{}/{}/stargazers", client.base_url, user, repo),
+        Some(vec![("Accept", STAR_ACCEPT_HEADER)]))
+}
+
+/// List the users that starred a repository owned by
+/// the user defined in `Client` as `user_agent`.
+pub fn list_my_stargazers(client: &Client, repo: &str) -> StargazerReturnType {
+    list_stargazers(client, &client.user_agent, repo)
+}
+
+/// List the repositories starred by a user.
+pub fn list_starred_repos(client: &Client, user: &str) -> StarredReturnType {
+    ::http::get(
+        client,
+        &format!("{}users/{}/starred", client.base_url, user),
+        None)
+}
+
+/// List the repositories starred by a user,
+/// together with the moment they were starred.
+pub fn list_starred_repos_with_timestamps(client: &Client, user: &str) -> StarredWithTimestampReturnType {
+    ::http::get(
+        client,
+        &format!("{}users/{}/starred", client.base_url, user),
+        Some(vec![("Accept", STAR_ACCEPT_HEADER)]))
+}
+
+/// List the repositories starred by
+/// the user defined in `Client` as `user_agent`.
+pub fn list_my_starred_repos(client: &Client) -> StarredReturnType {
+    list_starred_repos(client, &client.user_agent)
+}
+
+/// Check if a repository is starred by the authenticated user.
+pub fn is_starred(client: &Client, user: &str, repo: &str) -> Result<bool, ClientError> {
+    // GitHub answers with a `204 No Content` when the repository
+    // is starred, and with a `404 Not Found` when it isn't.
+    match ::http::request::<(), ()>(
+        client,
+        Method::Get,
+        &format!("{}user/starred/{}/{}", client.base_url, user, repo),
+        None,
+        None) {
+        Ok(..) => Ok(true),
+        Err(ClientError::Http(ref e)) if e.code == ErrorStatus::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Star a repository as the authenticated user.
+pub fn star_repo(client: &Client, user: &str, repo: &str) -> Result<Response, ClientError> {
+    ::http::request::<(), ()>(
+        client,
+        Method::Put,
+        &format!("{}user/starred/{}/{}", client.base_url, user, repo),
+        None,
+        None).map(|(_, resp)| resp)
+}
+
+/// Unstar a repository as the authenticated user.
+pub fn unstar_repo(client: &Client, user: &str, repo: &str) -> Result<Response, ClientError> {
+    ::http::delete(
+        client,
+        &format!("{}user/starred/{}/{}", client.base_url, user, repo))
+}
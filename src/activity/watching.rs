@@ -0,0 +1,118 @@
+use Client;
+
+use activity::Repository;
+use activity::payloads::User;
+
+use error::ClientError;
+use response::{decode_optional_datetime, Response};
+
+use chrono::{DateTime, UTC};
+
+use rustc_serialize::Decoder;
+use rustc_serialize::Decodable;
+
+/// Documentation References:
+/// https://developer.github.com/v3/activity/watching/
+
+/// `RepositorySubscription` represents the subscription
+/// of the authenticated user to the notifications of a repository.
+#[derive(Debug)]
+pub struct RepositorySubscription {
+    /// true when notifications are received for the repository
+    pub subscribed: bool,
+    /// true when all notifications of the repository are blocked
+    pub ignored: bool,
+    pub reason: Option<String>,
+    pub created_at: Option<DateTime<UTC>>,
+    pub url: String,
+    pub repository_url: String,
+}
+
+/// Allowing `RepositorySubscription` to be decoded from json values,
+/// decoding the `created_at` key as a timestamp.
+impl Decodable for RepositorySubscription {
+    fn decode<D: Decoder>(d: &mut D) -> Result<RepositorySubscription, D::Error> {
+        d.read_struct("RepositorySubscription", 6, |d| {
+            Ok(RepositorySubscription {
+                subscribed: try!(d.read_struct_field("subscribed", 0, Decodable::decode)),
+                ignored: try!(d.read_struct_field("ignored", 1, Decodable::decode)),
+                reason: try!(d.read_struct_field("reason", 2, Decodable::decode)),
+                created_at: try!(d.read_struct_field("created_at", 3, decode_optional_datetime)),
+                url: try!(d.read_struct_field("url", 4, Decodable::decode)),
+                repository_url: try!(d.read_struct_field("repository_url", 5, Decodable::decode)),
+            })
+        })
+    }
+}
+
+/// `RepositorySubscriptionRequest` is the body sent to set a repository subscription.
+#[derive(RustcEncodable)]
+struct RepositorySubscriptionRequest {
+    subscribed: bool,
+    ignored: bool,
+}
+
+/// `WatcherReturnType` is the return type for the requests listing watchers.
+pub type WatcherReturnType = Result<(Vec<User>, Response), ClientError>;
+/// `WatchedReturnType` is the return type for the requests listing watched repositories.
+pub type WatchedReturnType = Result<(Vec<Repository>, Response), ClientError>;
+/// `RepositorySubscriptionReturnType` is the return type for repository-subscription-requests.
+pub type RepositorySubscriptionReturnType = Result<(RepositorySubscription, Response), ClientError>;
+
+/// List the users watching a repository.
+pub fn list_watchers(client: &Client, user: &str, repo: &str) -> WatcherReturnType {
+    ::http::get(
+        client,
+        &format!("{}repos/{}/{}/subscribers", client.base_url, user, repo),
+        None)
+}
+
+/// List the users watching a repository owned by
+/// the user defined in `Client` as `user_agent`.
+pub fn list_my_watchers(client: &Client, repo: &str) -> WatcherReturnType {
+    list_watchers(client, &client.user_agent, repo)
+}
+
+/// List the repositories watched by a user.
+pub fn list_watched_repos(client: &Client, user: &str) -> WatchedReturnType {
+    ::http::get(
+        client,
+        &format!("{}users/{}/subscriptions", client.base_url, user),
+        None)
+}
+
+/// List the repositories watched by
+/// the user defined in `Client` as `user_agent`.
+pub fn list_my_watched_repos(client: &Client) -> WatchedReturnType {
+    list_watched_repos(client, &client.user_agent)
+}
+
+/// Get the subscription of the authenticated user to a repository,
+/// which is given as a `404 Not Found` when the user isn't subscribed.
+pub fn get_repo_subscription(client: &Client, user: &str, repo: &str) -> RepositorySubscriptionReturnType {
+    ::http::get_one(
+        client,
+        &format!("{}repos/{}/{}/subscription", client.base_url, user, repo),
+        None)
+}
+
+/// Set the subscription of the authenticated user to a repository,
+/// watching it when `subscribed` is true, or blocking all of
+/// its notifications when `ignored` is true.
+pub fn set_repo_subscription(client: &Client, user: &str, repo: &str, subscribed: bool, ignored: bool) -> RepositorySubscriptionReturnType {
+    ::http::put(
+        client,
+        &format!("{}repos/{}/{}/subscription", client.base_url, user, repo),
+        &RepositorySubscriptionRequest {
+            subscribed: subscribed,
+            ignored: ignored,
+        })
+}
+
+/// Delete the subscription of the authenticated user to a repository,
+/// which stops watching it.
+pub fn delete_repo_subscription(client: &Client, user: &str, repo: &str) -> Result<Response, ClientError> {
+    ::http::delete(
+        client,
+        &format!("{}repos/{}/{}/subscription", client.base_url, user, repo))
+}
@@ -0,0 +1,213 @@
+use Client;
+
+use ::http::Method;
+
+use ::error::*;
+
+use std::str;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, UTC};
+
+use openssl::crypto::hash;
+use openssl::crypto::rsa::RSA;
+
+use rustc_serialize::json;
+use rustc_serialize::base64::{ToBase64, URL_SAFE};
+
+/// Documentation References:
+/// https://developer.github.com/apps/building-github-apps/authenticating-with-github-apps/
+
+/// The Accept header value required to use the GitHub Apps API.
+static APP_ACCEPT_HEADER: &'static str = "application/vnd.github.machine-man-preview+json";
+/// The (constant) JOSE header of each JWT, as they are always signed using RS256.
+static JWT_HEADER: &'static str = r#"{"alg":"RS256","typ":"JWT"}"#;
+
+/// A JWT can be valid for at most 10 minutes, we stay a bit below that.
+const JWT_EXPIRATION_SECONDS: i64 = 9 * 60;
+/// A JWT is issued slightly in the past to allow for clock drift.
+const JWT_CLOCK_DRIFT_SECONDS: i64 = 60;
+/// An installation token is refreshed once it expires within this margin.
+const TOKEN_REFRESH_MARGIN_SECONDS: i64 = 5 * 60;
+/// Given when an installation access token was created succesfully.
+const STATUS_CREATED: u32 = 201;
+
+/// `Claims` represents the payload of the JWT used to authenticate as a GitHub App.
+#[derive(RustcEncodable)]
+struct Claims {
+    /// issued at, in seconds since the epoch
+    iat: i64,
+    /// expiration time, in seconds since the epoch
+    exp: i64,
+    /// issuer, which is the id of the GitHub App
+    iss: u64,
+}
+
+/// `AccessTokenResponse` represents the json response
+/// given when creating a new installation access token.
+#[derive(RustcDecodable)]
+struct AccessTokenResponse {
+    token: String,
+    expires_at: String,
+}
+
+/// `InstallationToken` is an access token that
+/// can be used to authenticate as a GitHub App installation.
+#[derive(Debug, Clone)]
+pub struct InstallationToken {
+    pub token: String,
+    pub expires_at: DateTime<UTC>,
+}
+
+/// `AppCredentials` authenticates requests as an installation of a GitHub App.
+/// A JWT signed with the private key of the app is traded for an
+/// installation access token, which is cached until it's about to expire.
+pub struct AppCredentials {
+    /// The id of the GitHub App.
+    pub app_id: u64,
+    /// The id of the installation of the GitHub App.
+    pub installation_id: u64,
+    /// The PEM encoded RSA private key of the GitHub App.
+    private_key: Vec<u8>,
+    /// The last installation token requested, if any.
+    token: Mutex<Option<InstallationToken>>,
+}
+
+impl AppCredentials {
+    /// Construct `AppCredentials` for an installation of a GitHub App,
+    /// using the PEM encoded private key generated for that App.
+    pub fn new(app_id: u64, installation_id: u64, private_key: &[u8]) -> AppCredentials {
+        AppCredentials {
+            app_id: app_id,
+            installation_id: installation_id,
+            private_key: private_key.to_vec(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Create a JWT, signed using RS256, that authenticates as the GitHub App itself.
+    pub fn jwt(&self) -> Result<String, ClientError> {
+        let now = UTC::now().timestamp();
+        let claims = Claims {
+            iat: now - JWT_CLOCK_DRIFT_SECONDS,
+            exp: now + JWT_EXPIRATION_SECONDS,
+            iss: self.app_id,
+        };
+        let claims = match json::encode(&claims) {
+            Ok(claims) => claims,
+            Err(e) => return InternalError::from_cause(e),
+        };
+
+        let message = format!("{}.{}",
+            JWT_HEADER.as_bytes().to_base64(URL_SAFE),
+            claims.as_bytes().to_base64(URL_SAFE));
+        let signature = try!(sign_rs256(&self.private_key, message.as_bytes()));
+        Ok(format!("{}.{}", message, signature.to_base64(URL_SAFE)))
+    }
+
+    /// Get an installation access token, requesting a new one
+    /// in case there is no cached token or it's about to expire.
+    pub fn installation_token(&self, client: &Client) -> Result<String, ClientError> {
+        let mut cached = match self.token.lock() {
+            Ok(cached) => cached,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let refresh_at = UTC::now() + Duration::seconds(TOKEN_REFRESH_MARGIN_SECONDS);
+        if let Some(ref token) = *cached {
+            if token.expires_at > refresh_at {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let token = try!(self.request_token(client));
+        *cached = Some(token.clone());
+        Ok(token.token)
+    }
+
+    /// Trade a fresh JWT for a new installation access token.
+    fn request_token(&self, client: &Client) -> Result<InstallationToken, ClientError> {
+        let url = format!("{}app/installations/{}/access_tokens",
+            client.base_url, self.installation_id);
+        self.exchange_jwt(client, &url).map_err(|e| e.for_request(Method::Post, &url))
+    }
+
+    /// Send the request trading a fresh JWT for a new installation access token to `url`.
+    fn exchange_jwt(&self, client: &Client, url: &str) -> Result<InstallationToken, ClientError> {
+        let jwt = try!(self.jwt());
+
+        // The token is requested via the transport of the client directly,
+        // as it has to be authenticated with the JWT rather than the credentials of the client.
+        let headers = vec![
+            ("User-Agent".to_string(), client.user_agent.clone()),
+            ("Accept".to_string(), APP_ACCEPT_HEADER.to_string()),
+            ("Authorization".to_string(), format!("Bearer {}", jwt)),
+        ];
+        let response = match client.transport.send(Method::Post, url, &headers, None) {
+            Ok(response) => response,
+            Err(e) => return TransportError::new(e),
+        };
+
+        if response.status != STATUS_CREATED {
+            return RequestError::new(response.status, &response.body);
+        }
+        let raw_body = match str::from_utf8(&response.body) {
+            Ok(raw_body) => raw_body,
+            Err(e) => return InternalError::from_cause(e),
+        };
+        let body: AccessTokenResponse = match json::decode(raw_body) {
+            Ok(body) => body,
+            Err(e) => return InternalError::from_cause(e),
+        };
+        match DateTime::parse_from_rfc3339(&body.expires_at) {
+            Ok(expires_at) => Ok(InstallationToken {
+                token: body.token,
+                expires_at: expires_at.with_timezone(&UTC),
+            }),
+            Err(e) => InternalError::from_cause(e),
+        }
+    }
+}
+
+/// Sign a message using RSASSA-PKCS1-v1_5 with SHA-256 (RS256),
+/// given a PEM encoded RSA private key.
+/// The key is read as an RSA key, so that any other kind of key,
+/// such as an EC key, is given as an error rather than failing to sign.
+fn sign_rs256(private_key: &[u8], message: &[u8]) -> Result<Vec<u8>, ClientError> {
+    let mut reader = private_key;
+    let key = match RSA::private_key_from_pem(&mut reader) {
+        Ok(key) => key,
+        Err(e) => return InternalError::from_cause(e),
+    };
+    // The message is signed as a digest, which has to be computed up front.
+    let digest = hash::hash(hash::Type::SHA256, message);
+    match key.sign(hash::Type::SHA256, &digest) {
+        Ok(signature) => Ok(signature),
+        Err(e) => InternalError::from_cause(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign_rs256;
+
+    use rustc_serialize::base64::{ToBase64, URL_SAFE};
+
+    static PRIVATE_KEY: &'static [u8] = include_bytes!("../../tests/data/app.pem");
+    /// A valid PEM encoded private key, which isn't an RSA key but an EC key.
+    static EC_PRIVATE_KEY: &'static [u8] = include_bytes!("../../tests/data/ec.pem");
+
+    #[test]
+    fn signs_using_rs256() {
+        // Signed via `openssl dgst -sha256 -sign tests/data/app.pem`.
+        let expected = "QmOwn7Rro-jPbhrtiFkVvQTJQ6j8SZftgLBvzG7MsZqmKjV_BnztJ3UE05i4q0t9f6UR9cv9hPVhFB0f-_dtcO62fanimkQyuo33Hs27cZWUQKOc42afIYF9Ncxif2mOUG8Kme5H3iSBpA9HbMLLNvRoX2eN9fZMSKkxgZSZBh5dFHgPhmMt7KpOBsigvCZ7HXzAJZiY3AdNzj_kwDmvn_AG-sF8SIjL6H_YzNo3Mq_c7v86z_v00cekBide6YcZ4sSnJ-CDSD_yH7oRuhcAUMoMcItKk70q8yg_FheZgfFk4eoJsrTtNWCgVme2SgTMrgaj5Cs0hhRZZVl-6lwdLw";
+        let signature = sign_rs256(PRIVATE_KEY, b"header.claims").unwrap();
+        assert_eq!(signature.to_base64(URL_SAFE), expected);
+    }
+
+    #[test]
+    fn rejects_an_invalid_key() {
+        assert!(sign_rs256(b"not a key", b"header.claims").is_err());
+        assert!(sign_rs256(EC_PRIVATE_KEY, b"header.claims").is_err());
+    }
+}
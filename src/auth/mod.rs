@@ -0,0 +1,58 @@
+use Client;
+
+use ::error::*;
+
+use rustc_serialize::base64::{ToBase64, STANDARD};
+
+pub use self::app::{AppCredentials, InstallationToken};
+
+/// Documentation References:
+/// https://developer.github.com/v3/#authentication
+/// https://developer.github.com/v3/auth/#working-with-two-factor-authentication
+
+pub mod app;
+
+/// `Credentials` enumerates the different ways a `Client`
+/// can authenticate itself to the GitHub API.
+/// Unauthenticated requests are limited to 60 requests per hour,
+/// while authenticated requests can make up to 5000 requests per hour.
+pub enum Credentials {
+    /// A personal access token, or any other OAuth2 token,
+    /// send as part of the Authorization header.
+    Token(String),
+    /// A username and password, send via HTTP Basic authentication.
+    /// Accounts with two-factor authentication enabled
+    /// also require a one-time password (OTP) to be given.
+    Basic {
+        username: String,
+        password: String,
+        otp: Option<String>,
+    },
+    /// An installation of a GitHub App, authenticated
+    /// via installation access tokens as documented by `AppCredentials`.
+    App(AppCredentials),
+}
+
+impl Credentials {
+    /// The value given under the Authorization key
+    /// as part of the header of each request made via the given `Client`.
+    pub fn authorization(&self, client: &Client) -> Result<String, ClientError> {
+        Ok(match *self {
+            Credentials::Token(ref token) => format!("token {}", token),
+            Credentials::Basic { ref username, ref password, .. } => {
+                let raw = format!("{}:{}", username, password);
+                format!("Basic {}", raw.as_bytes().to_base64(STANDARD))
+            }
+            Credentials::App(ref app) => format!("token {}", try!(app.installation_token(client))),
+        })
+    }
+
+    /// The one-time password given under the X-GitHub-OTP key
+    /// as part of the header of each request, if any.
+    pub fn otp(&self) -> Option<&str> {
+        match *self {
+            Credentials::Basic { otp: Some(ref otp), .. } => Some(otp),
+            _ => None,
+        }
+    }
+}
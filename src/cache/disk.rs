@@ -0,0 +1,223 @@
+use ::cache::{Cache, CacheEntry};
+use ::response::HttpHeaderType;
+
+use std::fs;
+use std::io;
+use std::process;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use filetime;
+use filetime::FileTime;
+
+use rustc_serialize::json;
+use rustc_serialize::base64::{FromBase64, ToBase64, STANDARD};
+
+/// By default a `DiskCache` stores at most 50 MiB worth of entries.
+const DEFAULT_MAX_BYTES: u64 = 50 * 1024 * 1024;
+/// The extension used for the files of stored entries.
+static ENTRY_EXTENSION: &'static str = "json";
+/// The extension used for the temporary files entries are written to.
+static TMP_EXTENSION: &'static str = "tmp";
+/// Temporary files older than 10 minutes were left behind by a writer that crashed.
+const STALE_TMP_SECONDS: u64 = 10 * 60;
+
+/// Counts the temporary files created by this process,
+/// so that each of them gets a unique name.
+static TMP_COUNTER: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// `StoredEntry` is the representation of a `CacheEntry` as stored on disk.
+#[derive(RustcEncodable, RustcDecodable)]
+struct StoredEntry {
+    /// the key the entry is stored for, to detect collisions of file names
+    key: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    headers: HttpHeaderType,
+    /// the raw response body, encoded as base64
+    body: String,
+}
+
+/// `DiskCache` is a `Cache` storing its entries as files in a directory,
+/// so that they survive the restart of a process.
+///
+/// The total size of all entries is kept below a limit, and optionally so
+/// is the number of entries, by evicting the least recently used entries.
+/// Errors reading from or writing to disk are never given to the caller,
+/// a failure simply results in an entry not being (found in) the cache.
+pub struct DiskCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_entries: Option<usize>,
+    /// serializes all operations of this cache on the directory
+    lock: Mutex<()>,
+}
+
+impl DiskCache {
+    /// Open a `DiskCache` using the given directory,
+    /// which is created in case it doesn't exist yet.
+    /// Entries stored by earlier processes are reused.
+    pub fn open<P: AsRef<Path>>(dir: P) -> io::Result<DiskCache> {
+        try!(fs::create_dir_all(dir.as_ref()));
+        Ok(DiskCache {
+            dir: dir.as_ref().to_path_buf(),
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_entries: None,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Limit the total size of all entries to `max` bytes.
+    pub fn max_bytes(mut self, max: u64) -> DiskCache {
+        self.max_bytes = max;
+        self
+    }
+
+    /// Limit the number of entries to `max`.
+    pub fn max_entries(mut self, max: usize) -> DiskCache {
+        self.max_entries = Some(max);
+        self
+    }
+
+    /// The path of the file used to store the entry of a key,
+    /// named after a (FNV-1a) hash of that key.
+    fn path(&self, key: &str) -> PathBuf {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in key.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        self.dir.join(format!("{:016x}.{}", hash, ENTRY_EXTENSION))
+    }
+
+    /// Read the raw content of a file.
+    fn read(path: &Path) -> io::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        try!(try!(fs::File::open(path)).read_to_end(&mut content));
+        Ok(content)
+    }
+
+    /// Write the raw content of a file, via a temporary file, so that
+    /// an entry is never read while only part of it was written.
+    /// The temporary file is unique to the process and the write, as several
+    /// processes sharing the directory might write the same entry at once.
+    /// Writing a file also marks its entry as the most recently used one.
+    fn write(path: &Path, content: &[u8]) -> io::Result<()> {
+        let tmp = path.with_extension(format!("{}-{}.{}",
+            process::id(), TMP_COUNTER.fetch_add(1, Ordering::SeqCst), TMP_EXTENSION));
+        let result = fs::File::create(&tmp)
+            .and_then(|mut file| file.write_all(content))
+            .and_then(|_| fs::rename(&tmp, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        result
+    }
+
+    /// Mark the entry stored in a file as the most recently used one,
+    /// by updating its modification time.
+    fn touch(path: &Path) -> io::Result<()> {
+        let now = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(now) => FileTime::from_seconds_since_1970(now.as_secs(), now.subsec_nanos()),
+            Err(..) => return Ok(()),
+        };
+        filetime::set_file_times(path, now, now)
+    }
+
+    /// Remove a file, which might have been removed by another process already.
+    fn remove(path: &Path) -> io::Result<()> {
+        match fs::remove_file(path) {
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Evict the least recently used entries until all limits are respected,
+    /// and remove the stale temporary files left behind by crashed writers.
+    /// Other processes sharing the directory might remove files at any moment,
+    /// so files that disappear along the way are skipped.
+    fn evict(&self) -> io::Result<()> {
+        let now = SystemTime::now();
+        let mut entries = Vec::new();
+        for entry in try!(fs::read_dir(&self.dir)) {
+            let path = try!(entry).path();
+            let is_entry = path.extension().map_or(false, |ext| ext == ENTRY_EXTENSION);
+            let is_tmp = path.extension().map_or(false, |ext| ext == TMP_EXTENSION);
+            if !is_entry && !is_tmp {
+                continue;
+            }
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let modified = try!(metadata.modified());
+            if is_entry {
+                entries.push((modified, metadata.len(), path));
+            } else if now.duration_since(modified).map_or(false, |age| age.as_secs() >= STALE_TMP_SECONDS) {
+                try!(DiskCache::remove(&path));
+            }
+        }
+        // Sorting the entries from the most to the least recently used.
+        entries.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let mut bytes = 0;
+        for (index, (_, len, path)) in entries.into_iter().enumerate() {
+            bytes += len;
+            if bytes > self.max_bytes || self.max_entries.map_or(false, |max| index >= max) {
+                try!(DiskCache::remove(&path));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Cache for DiskCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let _lock = self.lock.lock();
+        let path = self.path(key);
+        let content = match DiskCache::read(&path) {
+            Ok(content) => content,
+            Err(..) => return None,
+        };
+        let stored: StoredEntry = match String::from_utf8(content).ok()
+            .and_then(|raw| json::decode(&raw).ok()) {
+            Some(stored) => stored,
+            None => return None,
+        };
+        if stored.key != key {
+            return None;
+        }
+        let body = match stored.body.from_base64() {
+            Ok(body) => body,
+            Err(..) => return None,
+        };
+
+        let _ = DiskCache::touch(&path);
+        Some(CacheEntry {
+            etag: stored.etag,
+            last_modified: stored.last_modified,
+            headers: stored.headers,
+            body: body,
+        })
+    }
+
+    fn set(&self, key: &str, entry: CacheEntry) {
+        let _lock = self.lock.lock();
+        let stored = StoredEntry {
+            key: key.to_string(),
+            etag: entry.etag,
+            last_modified: entry.last_modified,
+            headers: entry.headers,
+            body: entry.body.to_base64(STANDARD),
+        };
+        if let Ok(content) = json::encode(&stored) {
+            if DiskCache::write(&self.path(key), content.as_bytes()).is_ok() {
+                let _ = self.evict();
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use ::response::HttpHeaderType;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Documentation References:
+/// https://developer.github.com/v3/#conditional-requests
+
+/// `CacheEntry` is a response stored in a `Cache`, which can be reused
+/// when a conditional request tells us that the resource wasn't modified.
+/// The body is stored as it was received, so that it can be decoded as any type.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    /// the value of the ETag header, send as If-None-Match
+    pub etag: Option<String>,
+    /// the value of the Last-Modified header, send as If-Modified-Since
+    pub last_modified: Option<String>,
+    /// the raw response header
+    pub headers: HttpHeaderType,
+    /// the raw response body
+    pub body: Vec<u8>,
+}
+
+impl CacheEntry {
+    /// Construct a `CacheEntry` from a response,
+    /// `None` is returned when the response can't be used for conditional requests.
+    pub fn new(headers: &HttpHeaderType, body: &[u8]) -> Option<CacheEntry> {
+        let header = |key: &str| headers.get(key).and_then(|values| values.first()).cloned();
+        let etag = header("etag");
+        let last_modified = header("last-modified");
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+        Some(CacheEntry {
+            etag: etag,
+            last_modified: last_modified,
+            headers: headers.clone(),
+            body: body.to_vec(),
+        })
+    }
+}
+
+/// `Cache` stores the responses of get-requests, keyed by their url and media type,
+/// as the same url can be requested using different Accept headers, see `key`.
+/// A `Client` with a `Cache` makes conditional requests for urls found in the cache,
+/// and reuses the cached response when GitHub responds with `304 Not Modified`.
+/// Such responses don't count against the rate limit.
+pub trait Cache: Send + Sync {
+    /// Get the entry stored for a key, if any.
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+    /// Store an entry for a key, replacing any existing entry.
+    fn set(&self, key: &str, entry: CacheEntry);
+}
+
+/// The key under which the response of a get-request is cached,
+/// made of the requested url and the value of its Accept header.
+pub fn key(url: &str, accept: &str) -> String {
+    format!("{} {}", url, accept)
+}
+
+/// `MemoryCache` is a `Cache` keeping all its entries in memory,
+/// for as long as it lives.
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl MemoryCache {
+    /// Construct an empty `MemoryCache`.
+    pub fn new() -> MemoryCache {
+        MemoryCache { entries: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        match self.entries.lock() {
+            Ok(entries) => entries.get(key).cloned(),
+            Err(poisoned) => poisoned.into_inner().get(key).cloned(),
+        }
+    }
+
+    fn set(&self, key: &str, entry: CacheEntry) {
+        match self.entries.lock() {
+            Ok(mut entries) => entries.insert(key.to_string(), entry),
+            Err(poisoned) => poisoned.into_inner().insert(key.to_string(), entry),
+        };
+    }
+}
+
+pub mod disk;
+
+pub use self::disk::DiskCache;
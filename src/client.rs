@@ -1,3 +1,9 @@
+use auth::{AppCredentials, Credentials};
+use transport::{CurlTransport, Transport};
+use cache::Cache;
+use rate_limit::{RateLimitPolicy, RateLimiter};
+use retry::RetryPolicy;
+
 /// By default and in most scenarios, `DEFAULT_BASE_URL`
 /// will be the base url for requests via this Client library.
 static DEFAULT_BASE_URL: &'static str = "https://api.github.com/";
@@ -17,6 +23,21 @@ pub struct Client {
     pub base_url: String,
     /// The base url for upload requests.
     pub upload_url: String,
+    /// The `Credentials` used to authenticate each request to `base_url` or `upload_url`,
+    /// requests are made anonymously when no credentials are given.
+    pub credentials: Option<Credentials>,
+    /// The `Transport` used to send each request,
+    /// which is a `CurlTransport` by default.
+    pub transport: Box<Transport>,
+    /// The `Cache` used for conditional get-requests,
+    /// no responses are cached when no cache is given.
+    pub cache: Option<Box<Cache>>,
+    /// The `RateLimiter` tracking the rate limit of this `Client`,
+    /// which fails fast on requests that would exceed it by default.
+    pub rate_limiter: RateLimiter,
+    /// The `RetryPolicy` used to retry requests that failed because
+    /// of a transient problem, no requests are retried by default.
+    pub retry_policy: RetryPolicy,
 }
 
 impl Client {
@@ -26,6 +47,11 @@ impl Client {
             user_agent: user.to_string(),
             base_url: base_url.to_string(),
             upload_url: upload_url.to_string(),
+            credentials: None,
+            transport: Box::new(CurlTransport),
+            cache: None,
+            rate_limiter: RateLimiter::new(RateLimitPolicy::FailFast),
+            retry_policy: RetryPolicy::never(),
         }
     }
 
@@ -33,4 +59,57 @@ impl Client {
     pub fn new(user: &str) -> Client {
         Client::custom(user, DEFAULT_BASE_URL, DEFAULT_UPLOAD_BASE_URL)
     }
+
+    /// Send all requests made via this `Client` using the given `Transport`.
+    pub fn with_transport<T: Transport + 'static>(mut self, transport: T) -> Client {
+        self.transport = Box::new(transport);
+        self
+    }
+
+    /// Cache the responses of get-requests made via this `Client` in the given `Cache`,
+    /// so that later requests for the same url can be made conditionally.
+    pub fn with_cache<C: Cache + 'static>(mut self, cache: C) -> Client {
+        self.cache = Some(Box::new(cache));
+        self
+    }
+
+    /// Apply the given `RateLimitPolicy` to requests that would exceed the rate limit,
+    /// e.g. `RateLimitPolicy::Wait` to sleep until the rate limit resets.
+    pub fn with_rate_limit_policy(mut self, policy: RateLimitPolicy) -> Client {
+        self.rate_limiter.policy = policy;
+        self
+    }
+
+    /// Retry requests that failed because of a transient problem using the given `RetryPolicy`.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Client {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Authenticate all requests made via this `Client` using the given `Credentials`.
+    pub fn with_credentials(mut self, credentials: Credentials) -> Client {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Authenticate all requests made via this `Client` using a personal access token.
+    pub fn with_token(self, token: &str) -> Client {
+        self.with_credentials(Credentials::Token(token.to_string()))
+    }
+
+    /// Authenticate all requests made via this `Client` using a username and password.
+    /// The `otp` code is required for accounts with two-factor authentication enabled.
+    pub fn with_basic_auth(self, username: &str, password: &str, otp: Option<&str>) -> Client {
+        self.with_credentials(Credentials::Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+            otp: otp.map(|otp| otp.to_string()),
+        })
+    }
+
+    /// Authenticate all requests made via this `Client` as an installation of a GitHub App,
+    /// using the id of the App and installation and the PEM encoded private key of the App.
+    pub fn with_app(self, app_id: u64, installation_id: u64, private_key: &[u8]) -> Client {
+        self.with_credentials(Credentials::App(AppCredentials::new(app_id, installation_id, private_key)))
+    }
 }
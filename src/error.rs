@@ -1,9 +1,18 @@
 use rustc_serialize::json;
+use rustc_serialize::json::Json;
 use rustc_serialize::Decoder;
 use rustc_serialize::Decodable;
 
+use http::Method;
+use response::HttpHeaderType;
+
+use chrono::{DateTime, Duration, TimeZone, UTC};
+
+use std::cmp;
 use std::str;
 use std::fmt;
+use std::error::Error;
+use std::time::Duration as StdDuration;
 
 /// Documentation References:
 /// https://developer.github.com/v3/#client-errors
@@ -24,6 +33,9 @@ pub enum ErrorCode {
     /// This can happen in resources that must
     /// have some unique key (such as Label names).
     AlreadyExists,
+    /// This means the resource couldn't be processed for another reason,
+    /// which is explained by the `message` of the `ErrorContext`.
+    Custom,
     /// `Unknown(String)` is used as a last resort when an error code is unknown.
     /// This should never happen, please report/resolve the issue when it does happen.
     Unknown(String),
@@ -37,6 +49,7 @@ impl fmt::Display for ErrorCode {
             ErrorCode::MissingField => "required field on the resource has not been set",
             ErrorCode::Invalid => "the formatting of the field is invalid",
             ErrorCode::AlreadyExists => "another resource has the same value as this field",
+            ErrorCode::Custom => "custom error",
             ErrorCode::Unknown(ref s) => &s,
         };
 
@@ -54,6 +67,7 @@ impl Decodable for ErrorCode {
                 "missing_field" => ErrorCode::MissingField,
                 "invalid" => ErrorCode::Invalid,
                 "already_exists" => ErrorCode::AlreadyExists,
+                "custom" => ErrorCode::Custom,
                 unknown => ErrorCode::Unknown(unknown.to_string()),
             }),
             Err(err) => Err(err),
@@ -63,42 +77,105 @@ impl Decodable for ErrorCode {
 
 /// When a request was successful.
 const STATUS_OK: u32 = 200;
+/// When a request was successful and a new resource was created.
+const STATUS_CREATED: u32 = 201;
+/// When a request was accepted, but will be processed in the background.
+const STATUS_ACCEPTED: u32 = 202;
+/// When a request was successful and no content was given as a response.
+const STATUS_NO_CONTENT: u32 = 204;
+/// When a request was successful and the client should reset its view,
+/// e.g. when notifications were marked as read.
+const STATUS_RESET_CONTENT: u32 = 205;
+/// When a conditional request was made and the resource wasn't modified.
+const STATUS_NOT_MODIFIED: u32 = 304;
 /// There was a problem with the data sent with the request.
 const STATUS_BAD_REQUEST: u32 = 400;
+/// Given when the request requires (valid) authentication.
+const STATUS_UNAUTHORIZED: u32 = 401;
 /// Given as a response to requests the user has insufficient permissions for.
 const STATUS_FORBIDDEN: u32 = 403;
 /// Given when the info requested is not found because it
 /// either doesn't exist or because you are not authorized.
 const STATUS_NOT_FOUND: u32 = 404;
+/// Given when the request conflicts with the current state of the resource,
+/// e.g. when merging a pull request with a head that was modified.
+const STATUS_CONFLICT: u32 = 409;
+/// Given when a resource was permanently removed or its feature was disabled,
+/// e.g. when listing the issues of a repository with issues disabled.
+const STATUS_GONE: u32 = 410;
 /// Given when a field or resource couldn't be processed properly.
 const STATUS_UNPROCCESSABLE_ENTITY: u32 = 422;
+/// Given when too many requests were made within a short period of time.
+const STATUS_TOO_MANY_REQUESTS: u32 = 429;
+/// Given when access to the resource was blocked for legal reasons, e.g. a DMCA takedown.
+const STATUS_UNAVAILABLE_FOR_LEGAL_REASONS: u32 = 451;
+/// Given when something went wrong on the side of GitHub,
+/// server errors are all status codes in the range `[500, 600)`.
+const STATUS_INTERNAL_SERVER_ERROR: u32 = 500;
 
 /// When a negative status was given as a response to a request,
 /// there might be one or several error descriptions embedded in the
 /// body to tell more about the details of what was wrong.
 /// `ErrorContext` is the representation for each of the errors that are given.
+/// Errors with a `custom` code might only be described by a `message`.
 #[derive(RustcDecodable, Debug)]
 pub struct ErrorContext {
-    pub resource: String,
-    pub field: String,
+    pub resource: Option<String>,
+    pub field: Option<String>,
     pub code: ErrorCode,
+    pub message: Option<String>,
+}
+
+impl ErrorContext {
+    /// Decode an `ErrorContext` from a single entry of the `errors` list.
+    /// Some endpoints describe an error using just a string,
+    /// which is given as a `custom` error with that string as message.
+    fn from_json(entry: Json) -> Option<ErrorContext> {
+        match entry {
+            Json::String(message) => Some(ErrorContext {
+                resource: None,
+                field: None,
+                code: ErrorCode::Custom,
+                message: Some(message),
+            }),
+            entry => Decodable::decode(&mut json::Decoder::new(entry)).ok(),
+        }
+    }
 }
 
 /// Allowing `ErrorContext` to be printed via `{}` in a controlled manner.
 impl fmt::Display for ErrorContext {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Error found in {}.{}: {}", self.resource, self.field, self.code)
+        match (&self.resource, &self.field) {
+            (&Some(ref resource), &Some(ref field)) =>
+                try!(write!(f, "Error found in {}.{}: {}", resource, field, self.code)),
+            (&Some(ref resource), &None) =>
+                try!(write!(f, "Error found in {}: {}", resource, self.code)),
+            _ => try!(write!(f, "Error: {}", self.code)),
+        }
+        match self.message {
+            Some(ref message) => write!(f, " ({})", message),
+            None => Ok(()),
+        }
     }
 }
 
 /// `ErrorStatus` represents the status code given in the header of a negative response.
 /// Look at const definitions such as `STATUS_OK` for more information for each value.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ErrorStatus{
+    NotModified,
     BadRequest,
-    UnprocessableEntity,
+    Unauthorized,
     Forbidden,
     NotFound,
+    Conflict,
+    Gone,
+    UnprocessableEntity,
+    TooManyRequests,
+    UnavailableForLegalReasons,
+    /// `ServerError(u32)` is any status code in the range `[500, 600)`.
+    ServerError(u32),
     Unknown(u32),
 }
 
@@ -106,10 +183,17 @@ pub enum ErrorStatus{
 impl fmt::Display for ErrorStatus {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let (code, msg) = match *self {
+            ErrorStatus::NotModified => (STATUS_NOT_MODIFIED, "Not Modified"),
             ErrorStatus::BadRequest => (STATUS_BAD_REQUEST, "Bad Request"),
-            ErrorStatus::UnprocessableEntity => (STATUS_UNPROCCESSABLE_ENTITY, "Unprocessable Entity"),
+            ErrorStatus::Unauthorized => (STATUS_UNAUTHORIZED, "Unauthorized"),
             ErrorStatus::Forbidden => (STATUS_FORBIDDEN, "Forbidden Request"),
             ErrorStatus::NotFound => (STATUS_NOT_FOUND, "Not Found"),
+            ErrorStatus::Conflict => (STATUS_CONFLICT, "Conflict"),
+            ErrorStatus::Gone => (STATUS_GONE, "Gone"),
+            ErrorStatus::UnprocessableEntity => (STATUS_UNPROCCESSABLE_ENTITY, "Unprocessable Entity"),
+            ErrorStatus::TooManyRequests => (STATUS_TOO_MANY_REQUESTS, "Too Many Requests"),
+            ErrorStatus::UnavailableForLegalReasons => (STATUS_UNAVAILABLE_FOR_LEGAL_REASONS, "Unavailable For Legal Reasons"),
+            ErrorStatus::ServerError(e) => (e, "Server Error"),
             ErrorStatus::Unknown(e) => (e, "Unknown"),
         };
 
@@ -122,13 +206,72 @@ impl ErrorStatus {
     /// based on its constant value as defined by the official docs.
     pub fn new(code: u32) -> ErrorStatus {
         match code {
+            STATUS_NOT_MODIFIED => ErrorStatus::NotModified,
             STATUS_BAD_REQUEST => ErrorStatus::BadRequest,
+            STATUS_UNAUTHORIZED => ErrorStatus::Unauthorized,
             STATUS_FORBIDDEN => ErrorStatus::Forbidden,
-            STATUS_UNPROCCESSABLE_ENTITY => ErrorStatus::UnprocessableEntity,
             STATUS_NOT_FOUND => ErrorStatus::NotFound,
+            STATUS_CONFLICT => ErrorStatus::Conflict,
+            STATUS_GONE => ErrorStatus::Gone,
+            STATUS_UNPROCCESSABLE_ENTITY => ErrorStatus::UnprocessableEntity,
+            STATUS_TOO_MANY_REQUESTS => ErrorStatus::TooManyRequests,
+            STATUS_UNAVAILABLE_FOR_LEGAL_REASONS => ErrorStatus::UnavailableForLegalReasons,
+            code if code >= STATUS_INTERNAL_SERVER_ERROR && code < 600 => ErrorStatus::ServerError(code),
             unknown => ErrorStatus::Unknown(unknown),
         }
     }
+
+    /// Check if the same request might succeed when it's retried later,
+    /// which is the case for server errors and when too many requests were made.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            ErrorStatus::TooManyRequests | ErrorStatus::ServerError(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Check if the request failed because it wasn't (properly) authenticated,
+    /// or because the authenticated user has insufficient permissions.
+    /// Note that GitHub gives a `NotFound` rather than a `Forbidden` status
+    /// for private resources, in order not to confirm their existence.
+    pub fn is_auth_failure(&self) -> bool {
+        match *self {
+            ErrorStatus::Unauthorized | ErrorStatus::Forbidden => true,
+            _ => false,
+        }
+    }
+
+    /// Check if the request failed because too many requests were made.
+    /// Requests exceeding the rate limit with a `Forbidden` status
+    /// are given as a `RateLimitError` instead.
+    pub fn is_rate_limited(&self) -> bool {
+        *self == ErrorStatus::TooManyRequests
+    }
+}
+
+/// `FailedRequest` identifies the request that resulted in an error,
+/// so that it's clear which call failed when an error is logged.
+#[derive(Debug, Clone)]
+pub struct FailedRequest {
+    /// the HTTP method of the request
+    pub method: Method,
+    /// the full url of the request, with the credentials in its query masked
+    pub url: String,
+}
+
+/// Allowing `FailedRequest` to be printed via `{}` in a controlled manner.
+impl fmt::Display for FailedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)
+    }
+}
+
+/// Print the request that failed as a suffix of an error message, if it's known.
+fn fmt_request(f: &mut fmt::Formatter, request: &Option<FailedRequest>) -> fmt::Result {
+    match *request {
+        Some(ref request) => write!(f, " [{}]", request),
+        None => Ok(()),
+    }
 }
 
 /// `RequestError` will be returned as a `Result<T, ClientError>` in case
@@ -139,21 +282,52 @@ pub struct RequestError {
     /// `code` represents the given status code
     /// stored in the form of `ErrorStatus`.
     pub code: ErrorStatus,
+    /// `message` is the description of the problem as given by GitHub.
+    pub message: Option<String>,
+    /// `documentation_url` links to the documentation of the
+    /// requested endpoint, as given by GitHub.
+    pub documentation_url: Option<String>,
     /// In case detailed errors are available
     // they will be accessible via `errors`, stored as an `ErrorContext`.
     pub errors: Vec<ErrorContext>,
+    /// `headers` is the raw header of the negative response, e.g. to read
+    /// the X-Poll-Interval of a `304 Not Modified` response.
+    pub headers: HttpHeaderType,
+    /// `request` is the request that was responded to negatively.
+    pub request: Option<FailedRequest>,
 }
 
 impl RequestError {
     /// Simple way to construct a `Result<T, ClientError>` based on
     /// the status code given in the header and the body in a raw utf8 buffer.
+    /// The body is formatted as `{"message": ..., "documentation_url": ..., "errors": [...]}`,
+    /// where each of the keys is optional and error entries
+    /// that can't be decoded as an `ErrorContext` are skipped.
     pub fn new<T>(code: u32, buffer: &[u8]) -> Result<T, ClientError> {
+        RequestError::from_response(code, &HttpHeaderType::new(), buffer)
+    }
+
+    /// Construct a `Result<T, ClientError>` like `new` does,
+    /// keeping the raw header of the response as well.
+    pub fn from_response<T>(code: u32, raw_header: &HttpHeaderType, buffer: &[u8]) -> Result<T, ClientError> {
+        let mut body = match str::from_utf8(buffer).ok().and_then(|body| Json::from_str(body).ok()) {
+            Some(Json::Object(body)) => body,
+            _ => json::Object::new(),
+        };
+        let string = |value: Option<Json>| match value {
+            Some(Json::String(value)) => Some(value),
+            _ => None,
+        };
         Err(ClientError::Http(RequestError {
             code: ErrorStatus::new(code),
-            errors: match str::from_utf8(buffer) {
-                Err(..) => Vec::new(),
-                Ok(body) => json::decode(body).unwrap_or(Vec::new()),
+            message: string(body.remove("message")),
+            documentation_url: string(body.remove("documentation_url")),
+            errors: match body.remove("errors") {
+                Some(Json::Array(errors)) => errors.into_iter().filter_map(ErrorContext::from_json).collect(),
+                _ => Vec::new(),
             },
+            headers: raw_header.clone(),
+            request: None,
         }))
     }
 }
@@ -161,10 +335,17 @@ impl RequestError {
 /// Allowing `RequestError` to be printed via `{}` in a controlled manner.
 impl fmt::Display for RequestError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "HTTP Error: {}. Found {} error description(s)!", self.code, self.errors.len())
+        try!(write!(f, "HTTP Error: {}", self.code));
+        if let Some(ref message) = self.message {
+            try!(write!(f, " ({})", message));
+        }
+        try!(write!(f, ". Found {} error description(s)!", self.errors.len()));
+        fmt_request(f, &self.request)
     }
 }
 
+impl Error for RequestError {}
+
 /// `InternalError` will be given in the form of Result<T, ClientError> in
 /// case something went wrong within this Client Library.
 /// It replaces panics so that you can freely choose the behaviour.
@@ -175,23 +356,154 @@ pub struct InternalError {
     /// future versions of this library might store extra info
     /// where it would help the debugging of an error.
     pub msg: String,
+    /// `cause` is the underlying error, if any,
+    /// e.g. the error given when a body couldn't be decoded.
+    pub cause: Option<Box<Error + Send + Sync>>,
+    /// `request` is the request during which the problem occurred, if any.
+    pub request: Option<FailedRequest>,
 }
 
 impl InternalError {
     /// Simple way to construct a `Result<T, ClientError>` based on
     /// information known for an internal error.
     pub fn new<T>(msg: &str) -> Result<T, ClientError> {
-        Err(ClientError::Internal(InternalError { msg: msg.to_string() }))
+        Err(ClientError::Internal(InternalError {
+            msg: msg.to_string(),
+            cause: None,
+            request: None,
+        }))
+    }
+
+    /// Simple way to construct a `Result<T, ClientError>` based on
+    /// an underlying error, which is described by its own message.
+    pub fn from_cause<T, E: Error + Send + Sync + 'static>(cause: E) -> Result<T, ClientError> {
+        Err(ClientError::Internal(InternalError {
+            msg: format!("{}", cause),
+            cause: Some(Box::new(cause)),
+            request: None,
+        }))
     }
 }
 
 /// Allowing `InternalError` to be printed via `{}` in a controlled manner.
 impl fmt::Display for InternalError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Internal Error: {}", self.msg)
+        try!(write!(f, "Internal Error: {}", self.msg));
+        fmt_request(f, &self.request)
+    }
+}
+
+impl Error for InternalError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        self.cause.as_ref().map(|cause| &**cause as &(Error + 'static))
+    }
+}
+
+/// `TransportError` will be given in the form of Result<T, ClientError> in case
+/// a request couldn't be sent or no response was received,
+/// e.g. because of a DNS failure, a refused connection or a TLS error.
+/// These errors are often temporary, so it can make sense to retry the request.
+#[derive(Debug)]
+pub struct TransportError {
+    /// `cause` is the underlying error as given by the `Transport`,
+    /// which is a `curl::ErrCode` for the default `CurlTransport`.
+    pub cause: Box<Error + Send + Sync>,
+    /// `request` is the request that couldn't be sent.
+    pub request: Option<FailedRequest>,
+}
+
+impl TransportError {
+    /// Simple way to construct a `Result<T, ClientError>` based on
+    /// the error given by the `Transport` when sending a request.
+    pub fn new<T>(cause: Box<Error + Send + Sync>) -> Result<T, ClientError> {
+        Err(ClientError::Transport(TransportError {
+            cause: cause,
+            request: None,
+        }))
+    }
+}
+
+/// Allowing `TransportError` to be printed via `{}` in a controlled manner.
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "Transport Error: {}", self.cause));
+        fmt_request(f, &self.request)
     }
 }
 
+impl Error for TransportError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        Some(&*self.cause)
+    }
+}
+
+/// `OtpRequiredError` will be given in the form of Result<T, ClientError> in case
+/// the authenticated account has two-factor authentication enabled,
+/// and no or an invalid one-time password (OTP) was given with the request.
+/// Retry the request with the OTP code given as part of the `Credentials`.
+#[derive(Debug)]
+pub struct OtpRequiredError {
+    /// `delivery` describes how the OTP code is delivered to the user,
+    /// e.g. `sms` or `app`, as given by the X-GitHub-OTP header.
+    pub delivery: String,
+    /// `request` is the request that requires the OTP code.
+    pub request: Option<FailedRequest>,
+}
+
+impl OtpRequiredError {
+    /// Simple way to construct a `Result<T, ClientError>` based on
+    /// the delivery method given in the X-GitHub-OTP header.
+    pub fn new<T>(delivery: &str) -> Result<T, ClientError> {
+        Err(ClientError::OtpRequired(OtpRequiredError {
+            delivery: delivery.to_string(),
+            request: None,
+        }))
+    }
+}
+
+/// Allowing `OtpRequiredError` to be printed via `{}` in a controlled manner.
+impl fmt::Display for OtpRequiredError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "OTP Required: a two-factor authentication code was sent via {}", self.delivery));
+        fmt_request(f, &self.request)
+    }
+}
+
+impl Error for OtpRequiredError {}
+
+/// `RateLimitError` will be given in the form of Result<T, ClientError> in case
+/// the rate limit of the `Client` is exceeded, either the primary limit as given by
+/// the X-RateLimit headers or a secondary (abuse) limit as given by the Retry-After header.
+/// No requests should be made until `reset`, which is what the `Wait` policy does for you.
+#[derive(Debug)]
+pub struct RateLimitError {
+    /// `reset` is the moment from which requests can be made again.
+    pub reset: DateTime<UTC>,
+    /// `request` is the request that exceeded the rate limit.
+    pub request: Option<FailedRequest>,
+}
+
+impl RateLimitError {
+    /// Simple way to construct a `Result<T, ClientError>` based on
+    /// the moment from which requests can be made again.
+    pub fn new<T>(reset: DateTime<UTC>) -> Result<T, ClientError> {
+        Err(ClientError::RateLimited(RateLimitError {
+            reset: reset,
+            request: None,
+        }))
+    }
+}
+
+/// Allowing `RateLimitError` to be printed via `{}` in a controlled manner.
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "Rate Limit Error: no requests can be made until {}", self.reset));
+        fmt_request(f, &self.request)
+    }
+}
+
+impl Error for RateLimitError {}
+
 /// `ClientError` enumerates all the possible errors that a public
 /// client (request) function of this library might be given.
 #[derive(Debug)]
@@ -201,7 +513,51 @@ pub enum ClientError {
     Http(RequestError),
     /// Read the documentation for `InternalError`
     /// for more information on this error..
-    Internal(InternalError)
+    Internal(InternalError),
+    /// Read the documentation for `OtpRequiredError`
+    /// for more information on this error.
+    OtpRequired(OtpRequiredError),
+    /// Read the documentation for `TransportError`
+    /// for more information on this error.
+    Transport(TransportError),
+    /// Read the documentation for `RateLimitError`
+    /// for more information on this error.
+    RateLimited(RateLimitError),
+}
+
+impl ClientError {
+    /// The request that resulted in this error, if it's known.
+    pub fn request(&self) -> Option<&FailedRequest> {
+        match *self {
+            ClientError::Http(ref e) => e.request.as_ref(),
+            ClientError::Internal(ref e) => e.request.as_ref(),
+            ClientError::OtpRequired(ref e) => e.request.as_ref(),
+            ClientError::Transport(ref e) => e.request.as_ref(),
+            ClientError::RateLimited(ref e) => e.request.as_ref(),
+        }
+    }
+
+    /// Attach the request that resulted in this error,
+    /// unless a request was attached already.
+    /// The credentials in the query of the url, if any, are masked.
+    pub fn for_request(mut self, method: Method, url: &str) -> ClientError {
+        {
+            let request = match self {
+                ClientError::Http(ref mut e) => &mut e.request,
+                ClientError::Internal(ref mut e) => &mut e.request,
+                ClientError::OtpRequired(ref mut e) => &mut e.request,
+                ClientError::Transport(ref mut e) => &mut e.request,
+                ClientError::RateLimited(ref mut e) => &mut e.request,
+            };
+            if request.is_none() {
+                *request = Some(FailedRequest {
+                    method: method,
+                    url: ::http::redact_url(url),
+                });
+            }
+        }
+        self
+    }
 }
 
 /// Allowing `ClientError` to be printed via `{}` in a controlled manner.
@@ -210,6 +566,23 @@ impl fmt::Display for ClientError {
         match self {
             &ClientError::Http(ref e) => write!(f, "{}", e),
             &ClientError::Internal(ref e) => write!(f, "{}", e),
+            &ClientError::OtpRequired(ref e) => write!(f, "{}", e),
+            &ClientError::Transport(ref e) => write!(f, "{}", e),
+            &ClientError::RateLimited(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Allowing `ClientError` to be used as a `std::error::Error`,
+/// which is transparent in that its source is the source of the error it wraps.
+impl Error for ClientError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            ClientError::Http(ref e) => e.source(),
+            ClientError::Internal(ref e) => e.source(),
+            ClientError::OtpRequired(ref e) => e.source(),
+            ClientError::Transport(ref e) => e.source(),
+            ClientError::RateLimited(ref e) => e.source(),
         }
     }
 }
@@ -218,5 +591,249 @@ impl fmt::Display for ClientError {
 /// if a returned status code is positive.
 /// Which means that the request was succesful.
 pub fn check_status_code(code: u32) -> bool {
-    code == STATUS_OK
+    match code {
+        STATUS_OK | STATUS_CREATED | STATUS_ACCEPTED | STATUS_NO_CONTENT | STATUS_RESET_CONTENT => true,
+        _ => false,
+    }
+}
+
+/// Simplistic function internally used to check if a response
+/// tells us that the requested resource wasn't modified.
+pub fn check_not_modified(code: u32) -> bool {
+    code == STATUS_NOT_MODIFIED
+}
+
+/// Simplistic function internally used to check if a negative response
+/// was given because a two-factor authentication code is required.
+/// The delivery method of the OTP code is returned when that is the case.
+pub fn check_otp_required(code: u32, raw_header: &HttpHeaderType) -> Option<&str> {
+    if code != STATUS_UNAUTHORIZED {
+        return None;
+    }
+    // The header is formatted as `required; <delivery>`, e.g. `required; sms`.
+    raw_header.get("x-github-otp")
+        .and_then(|values| values.first())
+        .and_then(|value| {
+            let mut parts = value.splitn(2, ';');
+            match parts.next().map(str::trim) {
+                Some("required") => Some(parts.next().map_or("", str::trim)),
+                _ => None,
+            }
+        })
+}
+
+/// Simplistic function internally used to check if a negative response
+/// was given because a rate limit was exceeded. The moment from which
+/// requests can be made again is returned when that is the case.
+pub fn check_rate_limited(code: u32, raw_header: &HttpHeaderType) -> Option<DateTime<UTC>> {
+    if code != STATUS_FORBIDDEN && code != STATUS_TOO_MANY_REQUESTS {
+        return None;
+    }
+    let header = |name: &str| raw_header.get(name)
+        .and_then(|values| values.first())
+        .and_then(|value| value.trim().parse::<i64>().ok());
+
+    // A secondary (abuse) limit tells us how many seconds to wait,
+    // while the primary limit tells us when it resets.
+    // Values that are out of range are ignored, rather than trusted.
+    if let Some(seconds) = header("retry-after") {
+        return Duration::from_std(StdDuration::from_secs(cmp::max(seconds, 0) as u64)).ok()
+            .and_then(|wait| UTC::now().checked_add(wait));
+    }
+    match (header("x-ratelimit-remaining"), header("x-ratelimit-reset")) {
+        (Some(0), Some(reset)) => UTC.timestamp_opt(reset, 0).single(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode the `RequestError` given for a status code and a raw body.
+    fn request_error(code: u32, body: &str) -> RequestError {
+        match RequestError::new::<()>(code, body.as_bytes()) {
+            Err(ClientError::Http(e)) => e,
+            other => panic!("expected a request error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decodes_a_full_error_body() {
+        let e = request_error(422, r#"{
+            "message": "Validation Failed",
+            "errors": [
+                { "resource": "Issue", "field": "title", "code": "missing_field" },
+                { "resource": "Label", "field": "name", "code": "already_exists", "message": "name already exists" },
+                { "resource": "Issue", "field": "body", "code": "too_long" }
+            ],
+            "documentation_url": "https://developer.github.com/v3/issues/#create-an-issue"
+        }"#);
+        assert_eq!(e.code, ErrorStatus::UnprocessableEntity);
+        assert_eq!(e.message, Some("Validation Failed".to_string()));
+        assert_eq!(e.documentation_url, Some("https://developer.github.com/v3/issues/#create-an-issue".to_string()));
+        assert_eq!(e.errors.len(), 3);
+        assert_eq!(e.errors[0].resource, Some("Issue".to_string()));
+        assert_eq!(e.errors[0].field, Some("title".to_string()));
+        match e.errors[0].code {
+            ErrorCode::MissingField => {}
+            ref code => panic!("expected missing_field, got {:?}", code),
+        }
+        assert_eq!(e.errors[1].message, Some("name already exists".to_string()));
+        match e.errors[2].code {
+            ErrorCode::Unknown(ref code) => assert_eq!(code, "too_long"),
+            ref code => panic!("expected an unknown code, got {:?}", code),
+        }
+        assert_eq!(e.to_string(), "HTTP Error: status 422: Unprocessable Entity (Validation Failed). Found 3 error description(s)!");
+    }
+
+    #[test]
+    fn decodes_a_custom_error() {
+        let e = request_error(422, r#"{
+            "message": "Validation Failed",
+            "errors": [{ "code": "custom", "message": "No commits between master and master" }]
+        }"#);
+        assert_eq!(e.errors.len(), 1);
+        let context = &e.errors[0];
+        assert!(context.resource.is_none());
+        assert!(context.field.is_none());
+        match context.code {
+            ErrorCode::Custom => {}
+            ref code => panic!("expected a custom code, got {:?}", code),
+        }
+        assert_eq!(context.to_string(), "Error: custom error (No commits between master and master)");
+    }
+
+    #[test]
+    fn decodes_an_error_given_as_a_string() {
+        let e = request_error(422, r#"{
+            "message": "Validation Failed",
+            "errors": ["The listed users cannot be searched"]
+        }"#);
+        assert_eq!(e.errors.len(), 1);
+        match e.errors[0].code {
+            ErrorCode::Custom => {}
+            ref code => panic!("expected a custom code, got {:?}", code),
+        }
+        assert_eq!(e.errors[0].message, Some("The listed users cannot be searched".to_string()));
+    }
+
+    #[test]
+    fn decodes_a_body_without_errors() {
+        let e = request_error(404, r#"{"message":"Not Found","documentation_url":"https://developer.github.com/v3"}"#);
+        assert_eq!(e.code, ErrorStatus::NotFound);
+        assert_eq!(e.message, Some("Not Found".to_string()));
+        assert_eq!(e.documentation_url, Some("https://developer.github.com/v3".to_string()));
+        assert!(e.errors.is_empty());
+
+        // Bodies that aren't a json object are ignored.
+        for body in &["", "[]", "<html>Bad Gateway</html>"] {
+            let e = request_error(502, body);
+            assert_eq!(e.code, ErrorStatus::ServerError(502));
+            assert!(e.message.is_none());
+            assert!(e.documentation_url.is_none());
+            assert!(e.errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn classifies_status_codes() {
+        // The status, followed by whether it's retryable, an auth failure and rate limited.
+        let table = [
+            (304, ErrorStatus::NotModified, false, false, false),
+            (400, ErrorStatus::BadRequest, false, false, false),
+            (401, ErrorStatus::Unauthorized, false, true, false),
+            (403, ErrorStatus::Forbidden, false, true, false),
+            (404, ErrorStatus::NotFound, false, false, false),
+            (409, ErrorStatus::Conflict, false, false, false),
+            (410, ErrorStatus::Gone, false, false, false),
+            (422, ErrorStatus::UnprocessableEntity, false, false, false),
+            (429, ErrorStatus::TooManyRequests, true, false, true),
+            (451, ErrorStatus::UnavailableForLegalReasons, false, false, false),
+            (500, ErrorStatus::ServerError(500), true, false, false),
+            (502, ErrorStatus::ServerError(502), true, false, false),
+            (503, ErrorStatus::ServerError(503), true, false, false),
+            (599, ErrorStatus::ServerError(599), true, false, false),
+            (418, ErrorStatus::Unknown(418), false, false, false),
+            (600, ErrorStatus::Unknown(600), false, false, false),
+        ];
+        for &(code, status, retryable, auth_failure, rate_limited) in table.iter() {
+            assert_eq!(ErrorStatus::new(code), status, "status {}", code);
+            assert_eq!(status.is_retryable(), retryable, "is_retryable for {}", code);
+            assert_eq!(status.is_auth_failure(), auth_failure, "is_auth_failure for {}", code);
+            assert_eq!(status.is_rate_limited(), rate_limited, "is_rate_limited for {}", code);
+            assert!(status.to_string().starts_with(&format!("status {}: ", code)));
+        }
+    }
+
+    /// The header of a response, using lowercase names as keys.
+    fn header(values: &[(&str, &str)]) -> HttpHeaderType {
+        values.iter().map(|&(name, value)| (name.to_string(), vec![value.to_string()])).collect()
+    }
+
+    #[test]
+    fn detects_a_forbidden_status_exceeding_the_rate_limit() {
+        let exceeded = header(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1420124400")]);
+        assert_eq!(check_rate_limited(403, &exceeded).map(|reset| reset.timestamp()), Some(1420124400));
+        assert_eq!(check_rate_limited(429, &exceeded).map(|reset| reset.timestamp()), Some(1420124400));
+        // The same headers are given with any other status, which isn't rate limited.
+        assert!(check_rate_limited(404, &exceeded).is_none());
+
+        // A forbidden status without exceeding the rate limit is a permission problem.
+        let remaining = header(&[("x-ratelimit-remaining", "42"), ("x-ratelimit-reset", "1420124400")]);
+        assert!(check_rate_limited(403, &remaining).is_none());
+        assert!(check_rate_limited(403, &header(&[])).is_none());
+
+        // A secondary limit is given via the Retry-After header.
+        let retry_after = header(&[("retry-after", "60")]);
+        let reset = check_rate_limited(403, &retry_after).unwrap();
+        assert!(reset > UTC::now() + Duration::seconds(50));
+        assert!(reset <= UTC::now() + Duration::seconds(60));
+    }
+
+    #[test]
+    fn chains_the_source_of_an_error() {
+        use std::io;
+
+        let e = TransportError::new::<()>(Box::new(io::Error::new(io::ErrorKind::ConnectionRefused, "refused")))
+            .unwrap_err();
+        let source = e.source().unwrap();
+        assert_eq!(source.downcast_ref::<io::Error>().unwrap().kind(), io::ErrorKind::ConnectionRefused);
+
+        let cause = json::DecoderError::MissingFieldError("id".to_string());
+        let e = InternalError::from_cause::<(), _>(cause).unwrap_err();
+        match e.source().and_then(|source| source.downcast_ref::<json::DecoderError>()) {
+            Some(&json::DecoderError::MissingFieldError(ref field)) => assert_eq!(field, "id"),
+            other => panic!("expected the decode error as source, got {:?}", other),
+        }
+
+        // Errors that aren't caused by another error have no source.
+        assert!(InternalError::new::<()>("oops").unwrap_err().source().is_none());
+        assert!(RequestError::new::<()>(404, b"").unwrap_err().source().is_none());
+        assert!(OtpRequiredError::new::<()>("sms").unwrap_err().source().is_none());
+    }
+
+    #[test]
+    fn keeps_the_first_request_attached() {
+        let e = InternalError::new::<()>("oops").unwrap_err();
+        assert!(e.request().is_none());
+
+        let e = e.for_request(Method::Post, "https://api.github.com/app/installations/42/access_tokens")
+            .for_request(Method::Get, "https://api.github.com/events");
+        let request = e.request().unwrap();
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.url, "https://api.github.com/app/installations/42/access_tokens");
+        assert_eq!(e.to_string(), "Internal Error: oops [POST https://api.github.com/app/installations/42/access_tokens]");
+
+        for e in vec![RequestError::new::<()>(404, b"").unwrap_err(),
+                      OtpRequiredError::new::<()>("sms").unwrap_err(),
+                      RateLimitError::new::<()>(UTC::now()).unwrap_err(),
+                      TransportError::new::<()>(Box::new(json::DecoderError::EOF)).unwrap_err()] {
+            let e = e.for_request(Method::Post, "https://api.github.com/first")
+                .for_request(Method::Delete, "https://api.github.com/second");
+            let request = e.request().unwrap();
+            assert_eq!(request.method, Method::Post);
+            assert_eq!(request.url, "https://api.github.com/first");
+        }
+    }
 }
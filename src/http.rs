@@ -1,55 +1,368 @@
+use Client;
+
 use ::response::*;
 use ::error::*;
+use ::cache;
+use ::cache::CacheEntry;
+use ::rate_limit;
+use ::rate_limit::RateLimitPolicy;
 
 use std::str;
-
-use curl::http as curl_http;
+use std::fmt;
+use std::thread;
 
 use rustc_serialize::json;
+use rustc_serialize::json::Json;
 use rustc_serialize::Decodable;
+use rustc_serialize::Encodable;
 
 /// The `API_ACCEPT_HEADER` value is specified under the Accept header,
 /// to enforce the use of the supported GitHub API, which is version 3.
 static API_ACCEPT_HEADER: &'static str = "application/vnd.github.v3+json";
+/// The `JSON_CONTENT_TYPE` value is specified under the Content-Type
+/// header for all requests that send a json body.
+static JSON_CONTENT_TYPE: &'static str = "application/json";
+/// The query parameters carrying credentials, such as the token in the urls
+/// of the private feeds, whose values are masked by `redact_url`.
+static SECRET_QUERY_PARAMS: &'static [&'static str] = &["token", "access_token", "client_secret"];
 
-/// A simplistic function that wraps around the behaviour of an
-/// http get-request as defined in `curl`.
-/// As the library gets more complete, a more complete and complex
-/// approach might be needed.
-pub fn get<R: Decodable>(user: &str, url: &str, opts: Option<Vec<(&str, &str)>>) -> Result<(Vec<R>, Response), ClientError> {
-    // Creating an empty request with header info needed for all requests.
-    let mut handle = curl_http::handle();
-    let mut request = handle.get(url)
-        .header("User-Agent", user).header("Accept", API_ACCEPT_HEADER);
+/// `Method` enumerates the HTTP methods used for requests to the GitHub API.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    Get,
+    Post,
+    Patch,
+    Put,
+    Delete,
+}
+
+/// Allowing `Method` to be printed via `{}`.
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name: &str = match *self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Patch => "PATCH",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl Method {
+    /// Check if sending the same request several times
+    /// has the same effect as sending it once.
+    pub fn is_idempotent(&self) -> bool {
+        match *self {
+            Method::Get | Method::Put | Method::Delete => true,
+            Method::Post | Method::Patch => false,
+        }
+    }
+}
+
+/// Mask the values of the query parameters of a url that carry credentials,
+/// so that the url can be shown in errors and used as part of a cache key.
+pub fn redact_url(url: &str) -> String {
+    let (path, query) = match url.find('?') {
+        Some(index) => (&url[..index], &url[index + 1..]),
+        None => return url.to_string(),
+    };
+    let params: Vec<String> = query.split('&')
+        .map(|param| match param.find('=') {
+            Some(index) if SECRET_QUERY_PARAMS.contains(&&param[..index]) =>
+                format!("{}=REDACTED", &param[..index]),
+            _ => param.to_string(),
+        })
+        .collect();
+    format!("{}?{}", path, params.join("&"))
+}
+
+/// A simplistic function that wraps around the behaviour of an http request,
+/// sent via the `Transport` of the `Client` and shared by all request functions.
+/// The raw body is returned together with the populated `Response`,
+/// in case the request was succesful.
+fn send(client: &Client, method: Method, url: &str, opts: Option<Vec<(&str, &str)>>, body: Option<String>) -> Result<(Vec<u8>, Response), ClientError> {
+    // Creating the header info needed for all requests.
+    let mut headers = vec![
+        ("User-Agent".to_string(), client.user_agent.clone()),
+        ("Accept".to_string(), API_ACCEPT_HEADER.to_string()),
+    ];
+    if body.is_some() {
+        headers.push(("Content-Type".to_string(), JSON_CONTENT_TYPE.to_string()));
+    }
+
+    // Authenticating the request in case the client has credentials,
+    // which are only sent to the hosts of the client, and never to the other hosts
+    // linked to by a response, such as github.com for the private feeds.
+    let own_host = url.starts_with(&client.base_url[..]) || url.starts_with(&client.upload_url[..]);
+    if let (true, Some(credentials)) = (own_host, client.credentials.as_ref()) {
+        headers.push(("Authorization".to_string(), try!(credentials.authorization(client))));
+        if let Some(otp) = credentials.otp() {
+            headers.push(("X-GitHub-OTP".to_string(), otp.to_string()));
+        }
+    }
 
     // In case extre header options are needed,
-    // it can be defined and given via the `opts` parameter.
-    if opts.is_some() {
-        for (name, val) in opts.unwrap() {
-            request = request.header(name, val);
+    // it can be defined and given via the `opts` parameter,
+    // replacing the default value of a header, such as the Accept header.
+    let opts: Vec<(String, String)> = opts.unwrap_or_default().into_iter()
+        .map(|(name, val)| (name.to_string(), val.to_string()))
+        .collect();
+    for &(ref name, ref val) in &opts {
+        headers.retain(|&(ref key, _)| !key.eq_ignore_ascii_case(name));
+        headers.push((name.clone(), val.clone()));
+    }
+
+    // Making get-requests conditional in case a response for the url was cached,
+    // for the same media type, unless the conditional headers were given via `opts`.
+    // The credentials in the url are left out of the key, as a cache might be stored on disk.
+    let cache_key = {
+        let accept = headers.iter()
+            .find(|&&(ref key, _)| key.eq_ignore_ascii_case("Accept"))
+            .map_or("", |&(_, ref val)| &val[..]);
+        cache::key(&redact_url(url), accept)
+    };
+    let cached = match (method, client.cache.as_ref()) {
+        (Method::Get, Some(cache)) => cache.get(&cache_key),
+        _ => None,
+    };
+    if let Some(ref entry) = cached {
+        let conditions = vec![
+            ("If-None-Match", entry.etag.as_ref()),
+            ("If-Modified-Since", entry.last_modified.as_ref()),
+        ];
+        for (name, val) in conditions {
+            if let Some(val) = val {
+                if !headers.iter().any(|&(ref key, _)| key.eq_ignore_ascii_case(name)) {
+                    headers.push((name.to_string(), val.clone()));
+                }
+            }
         }
     }
 
-    // Executing the actual request via curl and storing the response.
-    let response = request.exec().unwrap();
-    // Retrieving the status code from the response object.
-    let status_code = response.get_code();
+    // Sending the actual request via the transport and storing the response,
+    // a failure to do so is given as a `TransportError`.
+    // Requests that would exceed the rate limit of their resource are throttled according
+    // to the `RateLimitPolicy`, which might retry a rate limited request once, after waiting.
+    // Transient failures are retried according to the `RetryPolicy`.
+    let resource = rate_limit::resource(client, url);
+    let mut attempts = 0;
+    let mut failures = 0;
+    let mut waited = false;
+    let response;
+    loop {
+        try!(client.rate_limiter.throttle(resource));
+        attempts += 1;
+        let raw = match client.transport.send(method, url, &headers, body.as_ref().map(|body| body.as_bytes())) {
+            Ok(raw) => raw,
+            Err(e) => {
+                failures += 1;
+                match client.retry_policy.backoff(method, failures) {
+                    Some(delay) => {
+                        thread::sleep(delay);
+                        continue;
+                    }
+                    None => return TransportError::new(e),
+                }
+            }
+        };
+        match client.rate_limiter.track(resource, raw.status, &raw.headers) {
+            Some(_) if client.rate_limiter.policy == RateLimitPolicy::Wait && !waited => waited = true,
+            Some(reset) => return RateLimitError::new(reset),
+            None => {
+                if client.retry_policy.is_retryable_status(raw.status) {
+                    failures += 1;
+                    if let Some(delay) = client.retry_policy.backoff(method, failures) {
+                        thread::sleep(delay);
+                        continue;
+                    }
+                }
+                response = raw;
+                break;
+            }
+        }
+    }
+
+    // Reusing the cached response when the resource wasn't modified,
+    // updated with the header info of the new response, such as the rate limit.
+    if check_not_modified(response.status) {
+        if let Some(entry) = cached {
+            let mut raw_header = entry.headers;
+            for (key, values) in response.headers {
+                raw_header.insert(key, values);
+            }
+            let mut resp = Response::populate(&raw_header);
+            resp.cached = true;
+            resp.attempts = attempts;
+            resp.request_headers = opts;
+            return Ok((entry.body, resp));
+        }
+    }
+
+    // Checking the status code in a controlled fashion,
+    // throwing an error in case the response was negative.
+    if !check_status_code(response.status) {
+        if let Some(otp) = check_otp_required(response.status, &response.headers) {
+            return OtpRequiredError::new(otp);
+        }
+        return RequestError::from_response(response.status, &response.headers, &response.body);
+    }
+
+    // Caching the response of get-requests, so later requests can be made conditionally.
+    if let (Method::Get, Some(cache)) = (method, client.cache.as_ref()) {
+        if let Some(entry) = CacheEntry::new(&response.headers, &response.body) {
+            cache.set(&cache_key, entry);
+        }
+    }
+    let mut resp = Response::populate(&response.headers);
+    resp.attempts = attempts;
+    resp.request_headers = opts;
+    Ok((response.body, resp))
+}
+
+/// `FromJson` is implemented by all types that can be decoded from a json response body.
+/// It's implemented for all `Decodable` types, and can be implemented manually
+/// for types that have to look at the json itself to decode it,
+/// such as an event with a payload whose type depends on the type of the event.
+pub trait FromJson: Sized {
+    /// Decode an object of this type from a json value.
+    fn from_json(json: Json) -> Result<Self, json::DecoderError>;
+}
 
-    // Decoding the header and body in a controlled fashion,
-    // throwing an error in case something went wrong internally,
-    // replacing a panic, or when a response was negative.
-    if !check_status_code(status_code) {
-        return RequestError::new(status_code, response.get_body());
+impl<T: Decodable> FromJson for T {
+    fn from_json(json: Json) -> Result<T, json::DecoderError> {
+        Decodable::decode(&mut json::Decoder::new(json))
     }
-    let raw_body = match str::from_utf8(response.get_body()) {
+}
+
+/// Parsing a raw json body in a controlled fashion,
+/// throwing an error in case something went wrong internally,
+/// replacing a panic. An empty body is parsed as `null`,
+/// so that responses without content can be decoded as `()`.
+fn parse(raw_body: &[u8]) -> Result<Json, ClientError> {
+    let raw_body = match str::from_utf8(raw_body) {
+        Ok(raw_body) if raw_body.trim().is_empty() => "null",
         Ok(raw_body) => raw_body,
-        Err(e) => return InternalError::new(&format!("{}", e)),
+        Err(e) => return InternalError::from_cause(e),
+    };
+    match Json::from_str(raw_body) {
+        Ok(body) => Ok(body),
+        Err(e) => InternalError::from_cause(e),
+    }
+}
+
+/// Decoding a raw json body as a single object in a controlled fashion.
+fn decode<R: FromJson>(raw_body: &[u8]) -> Result<R, ClientError> {
+    match R::from_json(try!(parse(raw_body))) {
+        Ok(body) => Ok(body),
+        Err(e) => InternalError::from_cause(e),
+    }
+}
+
+/// Decoding a raw json body as a list of objects in a controlled fashion.
+fn decode_list<R: FromJson>(raw_body: &[u8]) -> Result<Vec<R>, ClientError> {
+    let items = match try!(parse(raw_body)) {
+        Json::Array(items) => items,
+        body => return InternalError::from_cause(
+            json::DecoderError::ExpectedError("Array".to_string(), body.to_string())),
     };
-    match json::decode(raw_body) {
-        Ok(b) => {
-            let body: Vec<R> = b;
-            Ok((body, Response::populate(response.get_headers())))
-        },
-        Err(e) => InternalError::new(&format!("{}", e)),
+    let mut body = Vec::with_capacity(items.len());
+    for item in items {
+        match R::from_json(item) {
+            Ok(item) => body.push(item),
+            Err(e) => return InternalError::from_cause(e),
+        }
+    }
+    Ok(body)
+}
+
+/// Encoding a request body as json in a controlled fashion,
+/// throwing an error in case something went wrong internally.
+fn encode<B: Encodable>(body: &B) -> Result<String, ClientError> {
+    match json::encode(body) {
+        Ok(body) => Ok(body),
+        Err(e) => InternalError::from_cause(e),
+    }
+}
+
+/// Sending a request with an optional `body` encoded as json,
+/// in a controlled fashion shared by all request functions.
+fn exchange<B: Encodable>(client: &Client, method: Method, url: &str, opts: Option<Vec<(&str, &str)>>, body: Option<&B>) -> Result<(Vec<u8>, Response), ClientError> {
+    let body = match body {
+        Some(body) => Some(try!(encode(body))),
+        None => None,
+    };
+    send(client, method, url, opts, body)
+}
+
+/// A simplistic function that wraps around the behaviour of an
+/// http get-request, decoding a json list of objects.
+/// As the library gets more complete, a more complete and complex
+/// approach might be needed.
+pub fn get<R: FromJson>(client: &Client, url: &str, opts: Option<Vec<(&str, &str)>>) -> Result<(Vec<R>, Response), ClientError> {
+    exchange::<()>(client, Method::Get, url, opts, None)
+        .and_then(|(raw_body, resp)| Ok((try!(decode_list(&raw_body)), resp)))
+        .map_err(|e| e.for_request(Method::Get, url))
+}
+
+/// Wraps around the behaviour of an http get-request for resources
+/// given as a single json object, such as a repository or user,
+/// rather than a list of objects as is the case for `get`.
+pub fn get_one<R: FromJson>(client: &Client, url: &str, opts: Option<Vec<(&str, &str)>>) -> Result<(R, Response), ClientError> {
+    request::<(), R>(client, Method::Get, url, opts, None)
+}
+
+/// Wraps around the behaviour of an http get-request for resources
+/// that aren't given as json, such as Atom feeds,
+/// returning the raw body of the response as is.
+pub fn get_raw(client: &Client, url: &str, opts: Option<Vec<(&str, &str)>>) -> Result<(Vec<u8>, Response), ClientError> {
+    send(client, Method::Get, url, opts, None)
+        .map_err(|e| e.for_request(Method::Get, url))
+}
+
+/// A generic request function, sending an optional `body` encoded as json
+/// and decoding the json body of the response as a single object of type `R`.
+/// Responses without content, such as a `204 No Content`, can be decoded as `()`.
+/// Any error given is attached to the method and url of the request.
+pub fn request<B: Encodable, R: FromJson>(client: &Client, method: Method, url: &str, opts: Option<Vec<(&str, &str)>>, body: Option<&B>) -> Result<(R, Response), ClientError> {
+    exchange(client, method, url, opts, body)
+        .and_then(|(raw_body, resp)| Ok((try!(decode(&raw_body)), resp)))
+        .map_err(|e| e.for_request(method, url))
+}
+
+/// Wraps around the behaviour of an http request for responses
+/// whose body isn't given as json, or isn't needed, sending `body` encoded as json
+/// and returning the raw body of the response as is.
+pub fn request_raw<B: Encodable>(client: &Client, method: Method, url: &str, opts: Option<Vec<(&str, &str)>>, body: Option<&B>) -> Result<(Vec<u8>, Response), ClientError> {
+    exchange(client, method, url, opts, body)
+        .map_err(|e| e.for_request(method, url))
+}
+
+/// Wraps around the behaviour of an http post-request,
+/// sending `body` encoded as json.
+pub fn post<B: Encodable, R: FromJson>(client: &Client, url: &str, body: &B) -> Result<(R, Response), ClientError> {
+    request(client, Method::Post, url, None, Some(body))
+}
+
+/// Wraps around the behaviour of an http patch-request,
+/// sending `body` encoded as json.
+pub fn patch<B: Encodable, R: FromJson>(client: &Client, url: &str, body: &B) -> Result<(R, Response), ClientError> {
+    request(client, Method::Patch, url, None, Some(body))
+}
+
+/// Wraps around the behaviour of an http put-request,
+/// sending `body` encoded as json.
+pub fn put<B: Encodable, R: FromJson>(client: &Client, url: &str, body: &B) -> Result<(R, Response), ClientError> {
+    request(client, Method::Put, url, None, Some(body))
+}
+
+/// Wraps around the behaviour of an http delete-request,
+/// which in general responds without content.
+pub fn delete(client: &Client, url: &str) -> Result<Response, ClientError> {
+    match send(client, Method::Delete, url, None, None) {
+        Ok((_, resp)) => Ok(resp),
+        Err(e) => Err(e.for_request(Method::Delete, url)),
     }
 }
@@ -1,11 +1,20 @@
 extern crate curl;
 extern crate chrono;
+extern crate openssl;
+extern crate filetime;
 extern crate rustc_serialize;
 
 pub mod response;
 pub mod client;
+pub mod auth;
 pub mod error;
 pub mod http;
+pub mod transport;
+pub mod cache;
+pub mod mock;
+pub mod pagination;
+pub mod rate_limit;
+pub mod retry;
 
 pub use client::*;
 
@@ -0,0 +1,406 @@
+use Client;
+
+use ::http::Method;
+use ::response::HttpHeaderType;
+
+use std::io;
+use std::thread;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The default value given under the X-RateLimit-Limit header.
+const DEFAULT_RATE_LIMIT: u32 = 5000;
+/// The default value given under the X-RateLimit-Reset header, Friday 1 January 2100.
+const DEFAULT_RATE_RESET: i64 = 4102444800;
+/// The status given when the conditional headers of a request match the route.
+const STATUS_NOT_MODIFIED: u32 = 304;
+/// The status given for requests to routes that don't exist.
+const STATUS_NOT_FOUND: u32 = 404;
+
+/// `MockResponse` is a canned response, served by a `MockServer` for a specific route.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    /// the status code of the response
+    pub status: u32,
+    /// the header of the response, in the order they are given
+    pub headers: Vec<(String, String)>,
+    /// the raw response body
+    pub body: String,
+}
+
+impl MockResponse {
+    /// Construct a `200 OK` response with a json body,
+    /// including the rate limit headers given by GitHub with each response.
+    pub fn json(body: &str) -> MockResponse {
+        MockResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json; charset=utf-8".to_string())],
+            body: body.to_string(),
+        }.rate_limit(DEFAULT_RATE_LIMIT, DEFAULT_RATE_LIMIT - 1, DEFAULT_RATE_RESET)
+    }
+
+    /// Construct a `204 No Content` response.
+    pub fn no_content() -> MockResponse {
+        MockResponse::json("").status(204)
+    }
+
+    /// Respond using the given status code instead.
+    pub fn status(mut self, status: u32) -> MockResponse {
+        self.status = status;
+        self
+    }
+
+    /// Add a header to the response, replacing any existing header with the same name.
+    pub fn header(mut self, name: &str, value: &str) -> MockResponse {
+        self.headers.retain(|&(ref key, _)| !key.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the X-RateLimit headers of the response,
+    /// where `reset` is given in seconds since the epoch.
+    pub fn rate_limit(self, limit: u32, remaining: u32, reset: i64) -> MockResponse {
+        self.header("X-RateLimit-Limit", &limit.to_string())
+            .header("X-RateLimit-Remaining", &remaining.to_string())
+            .header("X-RateLimit-Reset", &reset.to_string())
+    }
+
+    /// Add a page link to the Link header of the response, e.g. `rel` `next`.
+    pub fn link(self, rel: &str, url: &str) -> MockResponse {
+        let link = format!("<{}>; rel=\"{}\"", url, rel);
+        let value = match self.get_header("Link") {
+            Some(links) => format!("{}, {}", links, link),
+            None => link,
+        };
+        self.header("Link", &value)
+    }
+
+    /// Set the ETag header of the response. Requests to the same route that
+    /// send a matching If-None-Match header are given a `304 Not Modified` response.
+    pub fn etag(self, etag: &str) -> MockResponse {
+        self.header("ETag", etag)
+    }
+
+    /// Set the Last-Modified header of the response. Requests to the same route that
+    /// send a matching If-Modified-Since header are given a `304 Not Modified` response.
+    pub fn last_modified(self, last_modified: &str) -> MockResponse {
+        self.header("Last-Modified", last_modified)
+    }
+
+    /// Get the value of a header of the response, if it was set.
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|&&(ref key, _)| key.eq_ignore_ascii_case(name))
+            .map(|&(_, ref value)| &value[..])
+    }
+}
+
+/// The json of a single (public) `WatchEvent` with the given id,
+/// as given by the GitHub Events API, to be served by a `MockResponse`.
+pub fn event_json(id: u64) -> String {
+    format!(r#"{{
+        "id": "{}",
+        "type": "WatchEvent",
+        "public": true,
+        "actor": {{
+            "id": 1,
+            "login": "glendc",
+            "gravatar_id": "",
+            "avatar_url": "https://avatars.githubusercontent.com/u/1?",
+            "url": "https://api.github.com/users/glendc"
+        }},
+        "repo": {{
+            "id": 42,
+            "name": "glendc/github-rust",
+            "url": "https://api.github.com/repos/glendc/github-rust"
+        }},
+        "payload": {{ "action": "started" }},
+        "created_at": "2015-01-01T15:00:00Z"
+    }}"#, id)
+}
+
+/// The json of a list of events with the given ids, see `event_json`,
+/// listed in the given order, which is from the most recent to the oldest one for GitHub.
+pub fn events_json(ids: &[u64]) -> String {
+    let events: Vec<String> = ids.iter().map(|&id| event_json(id)).collect();
+    format!("[{}]", events.join(","))
+}
+
+/// The json of a single issue event with the given id, renaming an issue,
+/// as given by the GitHub Events API, to be served by a `MockResponse`.
+pub fn issue_event_json(id: u64) -> String {
+    format!(r#"{{
+        "id": {0},
+        "url": "https://api.github.com/repos/glendc/github-rust/issues/events/{0}",
+        "actor": {{
+            "id": 1,
+            "login": "glendc",
+            "gravatar_id": "",
+            "avatar_url": "https://avatars.githubusercontent.com/u/1?",
+            "url": "https://api.github.com/users/glendc"
+        }},
+        "event": "renamed",
+        "commit_id": null,
+        "commit_url": null,
+        "created_at": "2015-01-01T15:00:00Z",
+        "rename": {{ "from": "Add events", "to": "Add the Events API" }}
+    }}"#, id)
+}
+
+/// `MockRequest` is a request as it was received by a `MockServer`.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// the HTTP method of the request, e.g. `GET`
+    pub method: String,
+    /// the path of the request, including its query
+    pub path: String,
+    /// the request header, using lowercase names as keys
+    pub headers: HttpHeaderType,
+    /// the raw request body
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    /// Get the first value of a request header, if it was given.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase())
+            .and_then(|values| values.first())
+            .map(|value| &value[..])
+    }
+}
+
+/// The state shared between a `MockServer` and the thread serving its requests.
+struct State {
+    /// the registered routes, with the HTTP method and path as key
+    routes: Mutex<HashMap<(String, String), MockResponse>>,
+    /// all requests received so far
+    requests: Mutex<Vec<MockRequest>>,
+    /// set once the `MockServer` is dropped
+    shutdown: AtomicBool,
+}
+
+/// Read a single HTTP/1.1 request from a connection, `None` is returned
+/// in case the connection was closed before a complete request was received.
+fn read_request(stream: &TcpStream) -> Option<MockRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return None,
+    };
+
+    let mut headers = HttpHeaderType::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+            return None;
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        let mut parts = line.splitn(2, ':');
+        if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+            headers.entry(name.trim().to_lowercase())
+                .or_insert_with(Vec::new)
+                .push(value.trim().to_string());
+        }
+    }
+
+    let length = headers.get("content-length")
+        .and_then(|values| values.first())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0u8; length];
+    if reader.read_exact(&mut body).is_err() {
+        return None;
+    }
+
+    Some(MockRequest {
+        method: method,
+        path: path,
+        headers: headers,
+        body: body,
+    })
+}
+
+/// Find the response registered for a request, preferring an exact match
+/// of the path including its query over a match of the path without its query.
+fn find_response(state: &State, request: &MockRequest) -> MockResponse {
+    let routes = match state.routes.lock() {
+        Ok(routes) => routes,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let path = request.path.splitn(2, '?').next().unwrap_or("");
+    let response = routes.get(&(request.method.clone(), request.path.clone()))
+        .or_else(|| routes.get(&(request.method.clone(), path.to_string())));
+
+    match response {
+        Some(response) => {
+            // Conditional requests are answered without a body when nothing changed.
+            let not_modified =
+                (response.get_header("ETag").is_some() &&
+                    response.get_header("ETag") == request.header("If-None-Match")) ||
+                (response.get_header("Last-Modified").is_some() &&
+                    response.get_header("Last-Modified") == request.header("If-Modified-Since"));
+            if not_modified {
+                let mut response = response.clone().status(STATUS_NOT_MODIFIED);
+                response.body = String::new();
+                response
+            } else {
+                response.clone()
+            }
+        }
+        None => MockResponse::json(r#"{"message":"Not Found","documentation_url":"https://developer.github.com/v3"}"#)
+            .status(STATUS_NOT_FOUND),
+    }
+}
+
+/// The reason phrase given with a status code in the status line of a response.
+fn reason_phrase(status: u32) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        205 => "Reset Content",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        410 => "Gone",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        451 => "Unavailable For Legal Reasons",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+/// Serve a single request of a connection, which is closed afterwards.
+fn serve(state: &State, mut stream: TcpStream) -> io::Result<()> {
+    let request = match read_request(&stream) {
+        Some(request) => request,
+        None => return Ok(()),
+    };
+    let response = find_response(state, &request);
+    match state.requests.lock() {
+        Ok(mut requests) => requests.push(request),
+        Err(poisoned) => poisoned.into_inner().push(request),
+    }
+
+    let mut raw = format!("HTTP/1.1 {} {}\r\n", response.status, reason_phrase(response.status));
+    for &(ref name, ref value) in &response.headers {
+        raw.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    raw.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", response.body.len()));
+    raw.push_str(&response.body);
+    try!(stream.write_all(raw.as_bytes()));
+    stream.flush()
+}
+
+/// `MockServer` is a local HTTP server listening on an ephemeral port,
+/// serving canned responses per route until it's dropped,
+/// so that requests made via this Client library can be tested offline.
+///
+/// Point a `Client` to it via `MockServer::client`, or via `Client::custom`
+/// using `MockServer::url` as base url, and register a `MockResponse` for
+/// each route that will be requested. Requests for unknown routes are
+/// given a `404 Not Found` response, just like GitHub would.
+///
+/// ```no_run
+/// use github::http::Method;
+/// use github::mock::{MockServer, MockResponse};
+/// use github::activity::events::list_events;
+///
+/// let server = MockServer::start().unwrap();
+/// server.route(Method::Get, "/events", MockResponse::json("[]").etag("\"abc\""));
+/// let client = server.client("glendc");
+/// let (events, resp) = list_events(&client).unwrap();
+/// assert!(events.is_empty());
+/// assert_eq!(resp.rate.remaining, 4999);
+/// ```
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+}
+
+impl MockServer {
+    /// Start a `MockServer` on an ephemeral port of the loopback interface.
+    pub fn start() -> io::Result<MockServer> {
+        let listener = try!(TcpListener::bind("127.0.0.1:0"));
+        let addr = try!(listener.local_addr());
+        let state = Arc::new(State {
+            routes: Mutex::new(HashMap::new()),
+            requests: Mutex::new(Vec::new()),
+            shutdown: AtomicBool::new(false),
+        });
+
+        let shared = state.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if shared.shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let _ = serve(&shared, stream);
+                }
+            }
+        });
+
+        Ok(MockServer {
+            addr: addr,
+            state: state,
+        })
+    }
+
+    /// The base url of the server, e.g. `http://127.0.0.1:41234/`.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Construct a `Client` that sends all its requests to this server.
+    pub fn client(&self, user: &str) -> Client {
+        Client::custom(user, &self.url(), &self.url())
+    }
+
+    /// Register the response served for requests with the given method and path.
+    /// The path is given without the base url, e.g. `/repos/glendc/github-rust/events`.
+    /// A path with a query only matches requests with exactly that query,
+    /// while a path without a query matches requests with any query.
+    pub fn route(&self, method: Method, path: &str, response: MockResponse) {
+        let mut routes = match self.state.routes.lock() {
+            Ok(routes) => routes,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        routes.insert((method.to_string(), path.to_string()), response);
+    }
+
+    /// All requests received by this server so far, in the order they were received.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        match self.state.requests.lock() {
+            Ok(requests) => requests.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.state.shutdown.store(true, Ordering::SeqCst);
+        // Waking up the listening thread, so that it notices the shutdown.
+        let _ = TcpStream::connect(self.addr);
+    }
+}
@@ -0,0 +1,129 @@
+use Client;
+
+use ::error::*;
+use ::response::*;
+use ::http::FromJson;
+
+use std::collections::VecDeque;
+
+/// `Paginator` is a lazy iterator over all the items of a paginated list request.
+/// Items are fetched one page at a time, following the `next` link
+/// found in the Link header of each response, until no pages are left
+/// or one of the optional limits has been reached.
+///
+/// When a request fails, its error is given as the last item of the iteration.
+pub struct Paginator<'a, R> {
+    client: &'a Client,
+    /// the url of the page that will be requested next, if any
+    next: Option<String>,
+    /// the extra headers sent with every page request, such as a custom Accept header
+    headers: Vec<(String, String)>,
+    /// the items of the current page that haven't been yielded yet
+    items: VecDeque<R>,
+    /// an error that will be yielded once all buffered items are consumed
+    error: Option<ClientError>,
+    max_items: Option<usize>,
+    max_pages: Option<usize>,
+    item_count: usize,
+    page_count: usize,
+}
+
+impl<'a, R: FromJson> Paginator<'a, R> {
+    /// Construct a `Paginator` that will start by requesting the given url.
+    pub fn new(client: &'a Client, url: &str) -> Paginator<'a, R> {
+        Paginator {
+            client: client,
+            next: Some(url.to_string()),
+            headers: Vec::new(),
+            items: VecDeque::new(),
+            error: None,
+            max_items: None,
+            max_pages: None,
+            item_count: 0,
+            page_count: 0,
+        }
+    }
+
+    /// Construct a `Paginator` continuing from a page that was already requested,
+    /// such as the result of any of the `activity::events::list_*` functions.
+    /// The other pages are requested using the same extra headers as that page.
+    pub fn from_page(client: &'a Client, page: Result<(Vec<R>, Response), ClientError>) -> Paginator<'a, R> {
+        let mut paginator = Paginator::new(client, "");
+        paginator.next = None;
+        paginator.push_page(page);
+        paginator
+    }
+
+    /// Send the given extra headers with every page request,
+    /// such as an Accept header for a custom media type.
+    pub fn headers(mut self, headers: Vec<(&str, &str)>) -> Paginator<'a, R> {
+        self.headers = headers.into_iter()
+            .map(|(name, val)| (name.to_string(), val.to_string()))
+            .collect();
+        self
+    }
+
+    /// Stop the iteration after `max` items have been yielded.
+    pub fn max_items(mut self, max: usize) -> Paginator<'a, R> {
+        self.max_items = Some(max);
+        self
+    }
+
+    /// Stop the iteration after `max` pages have been requested,
+    /// the page given to `from_page` counts as a requested page.
+    pub fn max_pages(mut self, max: usize) -> Paginator<'a, R> {
+        self.max_pages = Some(max);
+        self
+    }
+
+    /// Store the result of a requested page and remember where to find the next one.
+    fn push_page(&mut self, page: Result<(Vec<R>, Response), ClientError>) {
+        self.page_count += 1;
+        match page {
+            Ok((items, resp)) => {
+                self.items.extend(items);
+                self.next = resp.next.map(|page| page.url);
+                self.headers = resp.request_headers;
+            }
+            Err(err) => {
+                self.error = Some(err);
+                self.next = None;
+            }
+        }
+    }
+}
+
+impl<'a, R: FromJson> Iterator for Paginator<'a, R> {
+    type Item = Result<R, ClientError>;
+
+    fn next(&mut self) -> Option<Result<R, ClientError>> {
+        if self.max_items.map_or(false, |max| self.item_count >= max) {
+            return None;
+        }
+
+        // Request new pages until we find an item or run out of pages,
+        // as pages can in theory be empty while still linking to a next page.
+        while self.items.is_empty() && self.error.is_none() {
+            if self.max_pages.map_or(false, |max| self.page_count >= max) {
+                return None;
+            }
+            let url = match self.next.take() {
+                Some(url) => url,
+                None => return None,
+            };
+            let headers = self.headers.iter()
+                .map(|&(ref name, ref val)| (&name[..], &val[..]))
+                .collect();
+            let page = ::http::get(self.client, &url, Some(headers));
+            self.push_page(page);
+        }
+
+        match self.items.pop_front() {
+            Some(item) => {
+                self.item_count += 1;
+                Some(Ok(item))
+            }
+            None => self.error.take().map(Err),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use Client;
+
+use error::*;
+use response::{HttpHeaderType, Populatable, Rate, Response};
+
+use std::cmp;
+use std::thread;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, UTC};
+
+/// Documentation References:
+/// https://developer.github.com/v3/rate_limit/
+
+/// `RateLimitResources` contains the rate limit status
+/// of each of the resources of the GitHub API.
+#[derive(Debug, RustcDecodable)]
+pub struct RateLimitResources {
+    /// the limit for all requests, except those listed below
+    pub core: Rate,
+    /// the limit for the Search API
+    pub search: Rate,
+    /// the limit for the GraphQL API
+    pub graphql: Option<Rate>,
+    /// the limit for creating GitHub Apps from a manifest
+    pub integration_manifest: Option<Rate>,
+}
+
+/// `RateLimitResponse` represents the response of the `get_rate_limit` request.
+#[derive(Debug, RustcDecodable)]
+pub struct RateLimitResponse {
+    pub resources: RateLimitResources,
+    /// the limit for all requests, except those of the Search API,
+    /// deprecated in favor of `resources.core`
+    pub rate: Rate,
+}
+
+/// `RateLimitReturnType` is the return type for the rate-limit-request.
+pub type RateLimitReturnType = Result<(RateLimitResponse, Response), ClientError>;
+
+/// Get your current rate limit status for each resource.
+///
+/// Requesting your rate limit status does not count against your rate limit.
+pub fn get_rate_limit(client: &Client) -> RateLimitReturnType {
+    ::http::get_one(
+        client,
+        &format!("{}rate_limit", client.base_url),
+        None)
+}
+
+/// Waiting a bit longer than strictly required,
+/// as the reset is only given with a precision of seconds.
+const WAIT_MARGIN_SECONDS: i64 = 1;
+
+/// `RateLimitPolicy` defines what a `Client` does
+/// when a request would exceed its rate limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitPolicy {
+    /// Sleep until the rate limit resets before sending the request.
+    /// A request that is rejected because of a rate limit is retried once.
+    Wait,
+    /// Fail immediately with a `RateLimitError`, without sending the request.
+    FailFast,
+}
+
+/// The resource of all requests that aren't counted against another resource.
+pub static CORE_RESOURCE: &'static str = "core";
+/// The resource of the requests to the Search API.
+pub static SEARCH_RESOURCE: &'static str = "search";
+/// The resource of the requests to the GraphQL API.
+pub static GRAPHQL_RESOURCE: &'static str = "graphql";
+/// The resource of the requests creating GitHub Apps from a manifest.
+pub static INTEGRATION_MANIFEST_RESOURCE: &'static str = "integration_manifest";
+
+/// The resource a request to the given url is counted against,
+/// as each resource has a rate limit of its own.
+pub fn resource(client: &Client, url: &str) -> &'static str {
+    let path = if url.starts_with(&client.base_url) {
+        &url[client.base_url.len()..]
+    } else {
+        url
+    };
+    if path.starts_with("search/") {
+        SEARCH_RESOURCE
+    } else if path == "graphql" || path.starts_with("graphql?") {
+        GRAPHQL_RESOURCE
+    } else if path.starts_with("app-manifests/") {
+        INTEGRATION_MANIFEST_RESOURCE
+    } else {
+        CORE_RESOURCE
+    }
+}
+
+/// `TrackedLimits` are the limits of a `Client` as last seen in a response.
+#[derive(Default)]
+struct TrackedLimits {
+    /// the primary rate limit of each resource, keyed by its name
+    rates: HashMap<String, Rate>,
+    /// the primary rate limit given by the last response that contained one
+    last: Option<Rate>,
+    /// the moment until which a secondary (abuse) limit applies
+    retry_at: Option<DateTime<UTC>>,
+}
+
+/// `RateLimiter` tracks the rate limits of a `Client` as given by the
+/// responses of its requests, applying its `RateLimitPolicy`
+/// to requests that would exceed the limit of their resource.
+pub struct RateLimiter {
+    /// The `RateLimitPolicy` applied, which is `FailFast` by default.
+    pub policy: RateLimitPolicy,
+    limits: Mutex<TrackedLimits>,
+}
+
+impl RateLimiter {
+    /// Construct a `RateLimiter` applying the given policy.
+    pub fn new(policy: RateLimitPolicy) -> RateLimiter {
+        RateLimiter {
+            policy: policy,
+            limits: Mutex::new(TrackedLimits::default()),
+        }
+    }
+
+    /// Lock the tracked limits, even when another thread panicked while holding them.
+    fn limits<'a>(&'a self) -> MutexGuard<'a, TrackedLimits> {
+        match self.limits.lock() {
+            Ok(limits) => limits,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// The `Rate` as given by the last response that contained one, if any.
+    pub fn last_rate(&self) -> Option<Rate> {
+        self.limits().last.clone()
+    }
+
+    /// The `Rate` of the given resource, such as `core` or `search`,
+    /// as given by the last response for that resource, if any.
+    pub fn rate(&self, resource: &str) -> Option<Rate> {
+        self.limits().rates.get(resource).cloned()
+    }
+
+    /// The moment until which no requests can be made for the given resource,
+    /// if any is known. A `Rate` without a limit is ignored, as it was populated
+    /// from a response that didn't contain the X-RateLimit headers.
+    pub fn blocked_until(&self, resource: &str) -> Option<DateTime<UTC>> {
+        let limits = self.limits();
+        let exhausted = limits.rates.get(resource)
+            .and_then(|rate| if rate.limit > 0 && rate.remaining == 0 { Some(rate.reset) } else { None });
+        match (exhausted, limits.retry_at) {
+            (Some(reset), Some(retry_at)) => Some(cmp::max(reset, retry_at)),
+            (reset, retry_at) => reset.or(retry_at),
+        }
+    }
+
+    /// Called before each request for the given resource, sleeping or failing
+    /// according to the policy in case the request would exceed the rate limit.
+    pub fn throttle(&self, resource: &str) -> Result<(), ClientError> {
+        let now = UTC::now();
+        let until = match self.blocked_until(resource) {
+            Some(until) if until > now => until,
+            _ => return Ok(()),
+        };
+        match self.policy {
+            RateLimitPolicy::FailFast => RateLimitError::new(until),
+            RateLimitPolicy::Wait => {
+                let wait = until - now + Duration::seconds(WAIT_MARGIN_SECONDS);
+                thread::sleep(wait.to_std().unwrap_or(StdDuration::from_secs(0)));
+                Ok(())
+            }
+        }
+    }
+
+    /// Called with each response to a request for the given resource, tracking
+    /// the limits it contains. The limits are tracked for the resource named by
+    /// the X-RateLimit-Resource header instead, when it's given.
+    /// The moment from which requests can be made again is returned
+    /// in case the request was rejected because of a rate limit.
+    pub fn track(&self, resource: &str, code: u32, raw_header: &HttpHeaderType) -> Option<DateTime<UTC>> {
+        let mut limits = self.limits();
+        if raw_header.contains_key("x-ratelimit-limit") {
+            let resource = raw_header.get("x-ratelimit-resource")
+                .and_then(|values| values.first())
+                .map_or(resource, |value| &value[..]);
+            let rate = Rate::populate(raw_header);
+            limits.rates.insert(resource.to_string(), rate.clone());
+            limits.last = Some(rate);
+        }
+        let reset = check_rate_limited(code, raw_header);
+        if reset.is_some() {
+            limits.retry_at = reset;
+        }
+        reset
+    }
+}
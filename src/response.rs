@@ -2,6 +2,11 @@ use std::collections::HashMap;
 use std::default::Default;
 use std::str::FromStr;
 
+use chrono::{DateTime, TimeZone, UTC};
+
+use rustc_serialize::Decoder;
+use rustc_serialize::Decodable;
+
 /// `HttpHeaderType` defines the type used for raw http headers
 pub type HttpHeaderType = HashMap<String, Vec<String>>;
 
@@ -15,25 +20,139 @@ pub trait Populatable {
 
 /// `Rate` represents the X-Rate-Limit data
 /// provided by the Github v3 API and provided for each response
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Rate {
     /// the maximum limit of requests
     pub limit: u32,
     /// remaining requests possible
     pub remaining: u32,
-    /// the date when this limit resets
-    /// TODO: replace with proper DateTime<T>
-    pub reset: String
+    /// the date when this limit resets,
+    /// which is the epoch in case it is unknown
+    pub reset: DateTime<UTC>,
+}
+
+/// Allowing `Rate` to be decoded from json values,
+/// as given for each resource by the `rate_limit` request.
+/// The `reset` key is given in seconds since the epoch.
+impl Decodable for Rate {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Rate, D::Error> {
+        d.read_struct("Rate", 3, |d| {
+            Ok(Rate {
+                limit: try!(d.read_struct_field("limit", 0, Decodable::decode)),
+                remaining: try!(d.read_struct_field("remaining", 1, Decodable::decode)),
+                reset: {
+                    let reset = try!(d.read_struct_field("reset", 2, Decodable::decode));
+                    match UTC.timestamp_opt(reset, 0).single() {
+                        Some(reset) => reset,
+                        None => return Err(d.error(&format!("invalid reset {}", reset))),
+                    }
+                },
+            })
+        })
+    }
+}
+
+/// Decode a timestamp formatted as defined by RFC 3339, e.g. `2011-04-14T16:00:49Z`,
+/// which is the format of all timestamps given in the json body of a response.
+pub fn decode_datetime<D: Decoder>(d: &mut D) -> Result<DateTime<UTC>, D::Error> {
+    let raw = try!(d.read_str());
+    match DateTime::parse_from_rfc3339(&raw) {
+        Ok(datetime) => Ok(datetime.with_timezone(&UTC)),
+        Err(e) => Err(d.error(&format!("invalid timestamp {}: {}", raw, e))),
+    }
+}
+
+/// Decode an optional timestamp, see `decode_datetime`,
+/// which is `None` when the value is `null` or missing.
+pub fn decode_optional_datetime<D: Decoder>(d: &mut D) -> Result<Option<DateTime<UTC>>, D::Error> {
+    d.read_option(|d, is_some| if is_some {
+        decode_datetime(d).map(Some)
+    } else {
+        Ok(None)
+    })
 }
 
 /// `Page` represents a link related to the response
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Page {
-    /// the actual page number
-    pub number: u64,
+    /// the actual page number, which is `None` for links
+    /// that paginate using a cursor rather than a `page` parameter
+    pub number: Option<u64>,
+    /// the full url used to request this page
+    pub url: String,
 }
 
-/// TODO: implement functionality
+impl Page {
+    /// Construct a `Page` from a url as found in the Link header,
+    /// taking the page number from its `page` parameter, if any.
+    fn from_url(url: &str) -> Page {
+        let query = url.find('?').map_or("", |index| &url[index+1..]);
+        let number = query.split('&')
+            .map(|pair| pair.splitn(2, '='))
+            .filter_map(|mut parts| match parts.next() {
+                Some("page") => parts.next().and_then(|number| number.parse().ok()),
+                _ => None,
+            })
+            .next();
+        Page {
+            number: number,
+            url: url.to_string(),
+        }
+    }
+}
+
+/// `Links` groups the different page links that can be
+/// found in the Link header of a (paginated) response.
+#[derive(Debug, Default)]
+struct Links {
+    next: Option<Page>,
+    last: Option<Page>,
+    first: Option<Page>,
+    prev: Option<Page>,
+}
+
+impl Populatable for Links {
+    /// `populate` the `Links` found in the Link header, formatted as:
+    /// `<https://api.github.com/...?page=2>; rel="next", <...?page=5>; rel="last"`
+    /// The urls are delimited by `<` and `>`, as they can contain commas themselves.
+    fn populate(raw_header: &HttpHeaderType) -> Links {
+        let mut pages: Links = Default::default();
+        let links = match raw_header.get("link") {
+            Some(links) => links,
+            None => return pages,
+        };
+        for value in links {
+            let mut rest = &value[..];
+            while let Some(start) = rest.find('<') {
+                let end = match rest[start..].find('>') {
+                    Some(end) => start + end,
+                    None => break,
+                };
+                let url = &rest[start+1..end];
+                rest = &rest[end+1..];
+                // the parameters of a link are given up to the start of the next one
+                let params = &rest[..rest.find('<').unwrap_or(rest.len())];
+                for param in params.split(|c| c == ';' || c == ',').map(str::trim) {
+                    if !param.starts_with("rel=") {
+                        continue;
+                    }
+                    // a single link can have several relations, e.g. rel="next last"
+                    for rel in param[4..].trim_matches('"').split(' ') {
+                        let slot = match rel {
+                            "next" => &mut pages.next,
+                            "last" => &mut pages.last,
+                            "first" => &mut pages.first,
+                            "prev" => &mut pages.prev,
+                            _ => continue,
+                        };
+                        *slot = Some(Page::from_url(url));
+                    }
+                }
+            }
+        }
+        pages
+    }
+}
 
 /// `Response` represents the exposed data given with each
 /// request and populated by the Github v3 API
@@ -51,14 +170,26 @@ pub struct Response {
     pub prev: Option<Page>,
     /// the latest X-Rate-Limit info
     pub rate: Rate,
+    /// true when GitHub responded with `304 Not Modified`
+    /// to a conditional request, and the cached body was used instead
+    pub cached: bool,
+    /// the number of attempts it took to get this response,
+    /// which is more than one when the request was retried
+    pub attempts: u32,
+    /// the number of seconds to wait before polling the same resource again,
+    /// as given by the X-Poll-Interval header of the Events API
+    pub poll_interval: Option<u64>,
+    /// the extra headers sent with the request via `opts`, such as a custom
+    /// Accept header, which have to be sent again to request the other pages
+    pub request_headers: Vec<(String, String)>,
 }
 
 /// Get a single raw header value for type `T`
-/// using its default value when str::parse failed
+/// using its default value when the header is missing or str::parse failed
 fn get_single_header_value<T>(raw_data: &HttpHeaderType, key: &str) -> T where T: Default + FromStr {
-    match str::parse(&raw_data[key][0]) {
-        Ok(x) => x,
-        Err(..) => Default::default(),
+    match raw_data.get(key).and_then(|values| values.first()).map(|value| str::parse(value)) {
+        Some(Ok(x)) => x,
+        _ => Default::default(),
     }
 }
 
@@ -68,22 +199,94 @@ impl Populatable for Rate {
         Rate {
             limit: get_single_header_value(raw_header, "x-ratelimit-limit"),
             remaining: get_single_header_value(raw_header, "x-ratelimit-remaining"),
-            reset: raw_header["x-ratelimit-reset"][0].clone(),
+            reset: UTC.timestamp_opt(get_single_header_value(raw_header, "x-ratelimit-reset"), 0)
+                .single()
+                .unwrap_or(UTC.timestamp(0, 0)),
         }
     }
 }
 
 impl Populatable for Response {
     /// `populate` a `Response<T>` object from the HTTP response header
-    /// TODO: populate pages properly
     fn populate(raw_header: &HttpHeaderType) -> Response {
+        let pages = Links::populate(raw_header);
         Response {
-            next: None,
-            last: None,
-            first: None,
-            prev: None,
+            next: pages.next,
+            last: pages.last,
+            first: pages.first,
+            prev: pages.prev,
             rate: Rate::populate(raw_header),
             resp: raw_header.clone(),
+            cached: false,
+            attempts: 1,
+            poll_interval: raw_header.get("x-poll-interval")
+                .and_then(|values| values.first())
+                .and_then(|value| value.parse().ok()),
+            request_headers: Vec::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(name: &str, values: &[&str]) -> HttpHeaderType {
+        let mut raw_header = HttpHeaderType::new();
+        raw_header.insert(name.to_string(), values.iter().map(|value| value.to_string()).collect());
+        raw_header
+    }
+
+    #[test]
+    fn links_with_several_relations() {
+        let resp = Response::populate(&header("link", &[
+            "<https://api.github.com/events?page=2>; rel=\"next\", \
+             <https://api.github.com/events?page=5>; rel=\"last\", \
+             <https://api.github.com/events?page=1>; rel=\"first prev\""]));
+        let next = resp.next.unwrap();
+        assert_eq!(next.number, Some(2));
+        assert_eq!(next.url, "https://api.github.com/events?page=2");
+        assert_eq!(resp.last.unwrap().number, Some(5));
+        assert_eq!(resp.first.unwrap().number, Some(1));
+        assert_eq!(resp.prev.unwrap().number, Some(1));
+    }
+
+    #[test]
+    fn links_with_commas_in_the_url() {
+        let resp = Response::populate(&header("link", &[
+            "<https://api.github.com/issues?labels=bug,ui&page=3>; rel=\"next\", \
+             <https://api.github.com/issues?labels=bug,ui&page=4>; rel=\"last\""]));
+        assert_eq!(resp.next.unwrap().url, "https://api.github.com/issues?labels=bug,ui&page=3");
+        let last = resp.last.unwrap();
+        assert_eq!(last.url, "https://api.github.com/issues?labels=bug,ui&page=4");
+        assert_eq!(last.number, Some(4));
+        assert!(resp.first.is_none());
+    }
+
+    #[test]
+    fn links_without_page_parameter() {
+        let resp = Response::populate(&header("link", &[
+            "<https://api.github.com/user/repos?after=Y3Vyc29y>; rel=\"next\""]));
+        let next = resp.next.unwrap();
+        assert_eq!(next.number, None);
+        assert_eq!(next.url, "https://api.github.com/user/repos?after=Y3Vyc29y");
+    }
+
+    #[test]
+    fn links_given_as_several_header_values() {
+        let resp = Response::populate(&header("link", &[
+            "<https://api.github.com/events?page=2>; rel=\"next\"",
+            "<https://api.github.com/events?page=9>; rel=\"last\""]));
+        assert_eq!(resp.next.unwrap().number, Some(2));
+        assert_eq!(resp.last.unwrap().number, Some(9));
+    }
+
+    #[test]
+    fn links_without_header() {
+        let resp = Response::populate(&HttpHeaderType::new());
+        assert!(resp.next.is_none());
+        assert!(resp.last.is_none());
+        assert!(resp.first.is_none());
+        assert!(resp.prev.is_none());
+    }
+}
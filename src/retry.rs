@@ -0,0 +1,130 @@
+use ::http::Method;
+
+use std::cmp;
+use std::default::Default;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The statuses retried by default, as they are given
+/// by GitHub (or a proxy in front of it) for transient failures.
+static DEFAULT_RETRYABLE_STATUSES: &'static [u32] = &[500, 502, 503, 504];
+/// By default the delay between two attempts never exceeds 30 seconds.
+const DEFAULT_MAX_DELAY_SECONDS: u64 = 30;
+/// The delay stops doubling after this many attempts, preventing an overflow.
+const MAX_BACKOFF_EXPONENT: u32 = 16;
+
+/// `RetryPolicy` defines how a `Client` retries requests that failed
+/// because of a transient problem, which is either a `TransportError`,
+/// such as a connection reset, or one of the `retryable_statuses`.
+///
+/// The delay between two attempts grows exponentially starting from `base_delay`,
+/// and when `jitter` is enabled a random part of that delay is taken instead,
+/// so that clients failing at the same moment don't retry at the same moment.
+/// Only requests using an idempotent method (GET, PUT and DELETE) are retried,
+/// unless `retry_non_idempotent` is enabled.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// the maximum number of attempts made for a single request,
+    /// including the first attempt, so `1` means no retries are made
+    pub max_attempts: u32,
+    /// the delay before the first retry, doubled for each next retry
+    pub base_delay: Duration,
+    /// the delay between two attempts is never longer than this
+    pub max_delay: Duration,
+    /// waiting a random delay between half and the full delay when enabled
+    pub jitter: bool,
+    /// the status codes of the responses that are retried
+    pub retryable_statuses: Vec<u32>,
+    /// retrying requests using a non-idempotent method (POST and PATCH) as well
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Construct a `RetryPolicy` that makes up to `max_attempts` attempts,
+    /// waiting `base_delay` before the first retry. Jitter is enabled,
+    /// and responses with a 500, 502, 503 or 504 status are retried.
+    pub fn new(max_attempts: u32, base_delay: Duration) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: max_attempts,
+            base_delay: base_delay,
+            max_delay: Duration::from_secs(DEFAULT_MAX_DELAY_SECONDS),
+            jitter: true,
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES.to_vec(),
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// Construct a `RetryPolicy` that never retries a request,
+    /// which is the policy used by default.
+    pub fn never() -> RetryPolicy {
+        RetryPolicy::new(1, Duration::from_secs(0))
+    }
+
+    /// Never wait longer than `max_delay` between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> RetryPolicy {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Enable or disable the jitter of the delay between two attempts.
+    pub fn jitter(mut self, jitter: bool) -> RetryPolicy {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Retry the responses with the given status codes, rather than the default ones.
+    pub fn retryable_statuses(mut self, statuses: &[u32]) -> RetryPolicy {
+        self.retryable_statuses = statuses.to_vec();
+        self
+    }
+
+    /// Enable or disable the retrying of requests using a non-idempotent method.
+    pub fn retry_non_idempotent(mut self, retry: bool) -> RetryPolicy {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Check if a response with the given status code should be retried.
+    pub fn is_retryable_status(&self, code: u32) -> bool {
+        self.retryable_statuses.contains(&code)
+    }
+
+    /// The delay to wait before retrying a request using the given method,
+    /// after `attempts` attempts failed. `None` is returned
+    /// when the request shouldn't be retried (anymore).
+    pub fn backoff(&self, method: Method, attempts: u32) -> Option<Duration> {
+        if attempts >= self.max_attempts || !(method.is_idempotent() || self.retry_non_idempotent) {
+            return None;
+        }
+        let factor = 1 << cmp::min(attempts.saturating_sub(1), MAX_BACKOFF_EXPONENT);
+        let delay = self.base_delay.checked_mul(factor)
+            .map_or(self.max_delay, |delay| cmp::min(delay, self.max_delay));
+        if !self.jitter {
+            return Some(delay);
+        }
+
+        // Taking a random part of the second half of the delay.
+        let half = delay / 2;
+        let nanos = half.as_secs() * 1_000_000_000 + half.subsec_nanos() as u64;
+        let extra = if nanos == 0 { 0 } else { random() % nanos };
+        Some(half + Duration::new(extra / 1_000_000_000, (extra % 1_000_000_000) as u32))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::never()
+    }
+}
+
+/// A pseudo-random number based on the current time, scrambled using xorshift,
+/// which is more than random enough to spread the retries of different clients.
+fn random() -> u64 {
+    let mut x = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(now) => now.as_secs() ^ ((now.subsec_nanos() as u64) << 32) ^ now.subsec_nanos() as u64,
+        Err(..) => 0,
+    } | 1;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
@@ -0,0 +1,66 @@
+use ::http::Method;
+use ::response::HttpHeaderType;
+
+use std::error::Error;
+
+use curl::http as curl_http;
+
+/// `RawResponse` is the raw response given by a `Transport` for a request,
+/// before any of it is interpreted by this Client library.
+#[derive(Debug)]
+pub struct RawResponse {
+    /// the status code of the response
+    pub status: u32,
+    /// the response header, using lowercase names as keys
+    pub headers: HttpHeaderType,
+    /// the raw response body
+    pub body: Vec<u8>,
+}
+
+/// `Transport` is the HTTP stack used by a `Client` to send its requests.
+/// It allows you to use any HTTP library of your choice,
+/// or to respond with in-memory responses while testing.
+/// `CurlTransport` is the `Transport` used by default.
+pub trait Transport: Send + Sync {
+    /// `send` a request and return the raw response of the server.
+    /// An error should only be given when no response could be received,
+    /// as negative responses are handled by this Client library itself.
+    fn send(&self, method: Method, url: &str, headers: &[(String, String)], body: Option<&[u8]>) -> Result<RawResponse, Box<Error + Send + Sync>>;
+}
+
+/// `CurlTransport` is a `Transport` sending requests via `curl`.
+pub struct CurlTransport;
+
+impl Transport for CurlTransport {
+    fn send(&self, method: Method, url: &str, headers: &[(String, String)], body: Option<&[u8]>) -> Result<RawResponse, Box<Error + Send + Sync>> {
+        let mut handle = curl_http::handle();
+        let empty: &[u8] = &[];
+        let mut request = match method {
+            Method::Get => handle.get(url),
+            Method::Post => handle.post(url, body.unwrap_or(empty)),
+            Method::Patch => handle.patch(url, body.unwrap_or(empty)),
+            Method::Put => handle.put(url, body.unwrap_or(empty)),
+            Method::Delete => match body {
+                Some(body) => handle.delete(url).body(body),
+                None => handle.delete(url),
+            },
+        };
+        for &(ref name, ref val) in headers {
+            // curl adds its own Content-Type header, unless it's given explicitly.
+            request = if name.to_lowercase() == "content-type" {
+                request.content_type(val)
+            } else {
+                request.header(name, val)
+            };
+        }
+
+        match request.exec() {
+            Ok(response) => Ok(RawResponse {
+                status: response.get_code(),
+                headers: response.get_headers().clone(),
+                body: response.move_body(),
+            }),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+extern crate github;
+extern crate chrono;
+extern crate rustc_serialize;
+
+use github::error::ClientError;
+use github::auth::AppCredentials;
+use github::http::Method;
+use github::mock::{MockResponse, MockServer};
+use github::activity::events::list_events;
+
+use chrono::{Duration, UTC};
+
+use rustc_serialize::json::Json;
+use rustc_serialize::base64::FromBase64;
+
+static PRIVATE_KEY: &'static [u8] = include_bytes!("data/app.pem");
+
+/// The response of the token endpoint, with a token expiring after the given duration.
+fn access_token(token: &str, expires_in: Duration) -> MockResponse {
+    let expires_at = (UTC::now() + expires_in).format("%Y-%m-%dT%H:%M:%SZ");
+    MockResponse::json(&format!(r#"{{"token":"{}","expires_at":"{}"}}"#, token, expires_at)).status(201)
+}
+
+/// Decode a base64url encoded part of a JWT as json.
+fn decode_part(part: &str) -> Json {
+    let raw = part.from_base64().unwrap();
+    Json::from_str(&String::from_utf8(raw).unwrap()).unwrap()
+}
+
+#[test]
+fn creates_a_signed_jwt() {
+    let jwt = AppCredentials::new(7, 42, PRIVATE_KEY).jwt().unwrap();
+    let parts: Vec<&str> = jwt.split('.').collect();
+    assert_eq!(parts.len(), 3);
+
+    let header = decode_part(parts[0]);
+    assert_eq!(header.find("alg").and_then(Json::as_string), Some("RS256"));
+    let claims = decode_part(parts[1]);
+    assert_eq!(claims.find("iss").and_then(Json::as_u64), Some(7));
+    let iat = claims.find("iat").and_then(Json::as_i64).unwrap();
+    let exp = claims.find("exp").and_then(Json::as_i64).unwrap();
+    assert!(iat < UTC::now().timestamp());
+    assert!(exp - iat <= 10 * 60);
+    assert_eq!(parts[2].from_base64().unwrap().len(), 256);
+}
+
+#[test]
+fn trades_the_jwt_for_a_cached_installation_token() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Post, "/app/installations/42/access_tokens", access_token("v1.abc", Duration::hours(1)));
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc").with_app(7, 42, PRIVATE_KEY);
+
+    list_events(&client).unwrap();
+    list_events(&client).unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].method, "POST");
+    let authorization = requests[0].header("Authorization").unwrap();
+    assert!(authorization.starts_with("Bearer "));
+    assert_eq!(authorization.split('.').count(), 3);
+    assert_eq!(requests[0].header("Accept"), Some("application/vnd.github.machine-man-preview+json"));
+    for request in &requests[1..] {
+        assert_eq!(request.path, "/events");
+        assert_eq!(request.header("Authorization"), Some("token v1.abc"));
+    }
+}
+
+#[test]
+fn refreshes_a_token_about_to_expire() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Post, "/app/installations/42/access_tokens", access_token("v1.abc", Duration::minutes(2)));
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc").with_app(7, 42, PRIVATE_KEY);
+
+    list_events(&client).unwrap();
+    list_events(&client).unwrap();
+
+    let methods: Vec<String> = server.requests().into_iter().map(|request| request.method).collect();
+    assert_eq!(methods, vec!["POST", "GET", "POST", "GET"]);
+}
+
+#[test]
+fn fails_when_the_token_is_refused() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Post, "/app/installations/42/access_tokens",
+                 MockResponse::json(r#"{"message":"A JSON web token could not be decoded"}"#).status(401));
+    let client = server.client("glendc").with_app(7, 42, PRIVATE_KEY);
+
+    match list_events(&client) {
+        Err(ClientError::Http(e)) => {
+            assert_eq!(e.message.as_ref().map(|msg| &msg[..]), Some("A JSON web token could not be decoded"));
+            let request = e.request.unwrap();
+            assert_eq!(request.method, Method::Post);
+            assert!(request.url.ends_with("/app/installations/42/access_tokens"));
+        }
+        other => panic!("expected a request error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+    assert_eq!(server.requests().len(), 1);
+}
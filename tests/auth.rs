@@ -0,0 +1,122 @@
+extern crate github;
+
+use github::error::{ClientError, ErrorStatus};
+use github::http::{self, Method};
+use github::mock::{MockResponse, MockServer};
+use github::pagination::Paginator;
+use github::activity::EventResponse;
+use github::activity::events::{list_events, list_my_organisation_events};
+
+#[test]
+fn sends_the_token_with_each_request() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    server.route(Method::Get, "/users/glendc/events/orgs/rust-lang", MockResponse::json("[]"));
+    let client = server.client("glendc").with_token("abc123");
+
+    list_events(&client).unwrap();
+    list_my_organisation_events(&client, "rust-lang").unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        assert_eq!(request.header("Authorization"), Some("token abc123"));
+        assert_eq!(request.headers.get("authorization").unwrap().len(), 1);
+    }
+}
+
+#[test]
+fn sends_no_credentials_without_a_token() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc");
+
+    list_events(&client).unwrap();
+    assert_eq!(server.requests()[0].header("Authorization"), None);
+}
+
+#[test]
+fn sends_no_token_to_other_hosts() {
+    let api = MockServer::start().unwrap();
+    let other = MockServer::start().unwrap();
+    other.route(Method::Get, "/raw", MockResponse::json("raw"));
+    let client = api.client("glendc").with_token("abc123");
+
+    let (body, _) = http::get_raw(&client, &format!("{}raw", other.url()), None).unwrap();
+    assert_eq!(body, b"raw");
+    assert_eq!(other.requests()[0].header("Authorization"), None);
+}
+
+#[test]
+fn sends_no_token_when_following_links_to_other_hosts() {
+    let api = MockServer::start().unwrap();
+    let other = MockServer::start().unwrap();
+    api.route(Method::Get, "/events", MockResponse::json("[]")
+        .link("next", &format!("{}events?page=2", other.url())));
+    other.route(Method::Get, "/events?page=2", MockResponse::json("[]"));
+    let client = api.client("glendc").with_token("abc123");
+
+    let events: Vec<_> = Paginator::<EventResponse>::from_page(&client, list_events(&client)).collect();
+    assert!(events.is_empty());
+    assert_eq!(api.requests()[0].header("Authorization"), Some("token abc123"));
+    let requests = other.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("Authorization"), None);
+}
+
+#[test]
+fn sends_basic_auth() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc").with_basic_auth("glendc", "secret", None);
+
+    list_events(&client).unwrap();
+    let request = &server.requests()[0];
+    // "glendc:secret" encoded as base64.
+    assert_eq!(request.header("Authorization"), Some("Basic Z2xlbmRjOnNlY3JldA=="));
+    assert_eq!(request.header("X-GitHub-OTP"), None);
+}
+
+#[test]
+fn sends_the_otp_code() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc").with_basic_auth("glendc", "secret", Some("123456"));
+
+    list_events(&client).unwrap();
+    let request = &server.requests()[0];
+    assert_eq!(request.header("Authorization"), Some("Basic Z2xlbmRjOnNlY3JldA=="));
+    assert_eq!(request.header("X-GitHub-OTP"), Some("123456"));
+}
+
+#[test]
+fn gives_a_required_otp_code_as_its_own_error() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json(r#"{"message":"Must specify two-factor authentication OTP code."}"#)
+        .status(401)
+        .header("X-GitHub-OTP", "required; sms"));
+    let client = server.client("glendc").with_basic_auth("glendc", "secret", None);
+
+    match list_events(&client) {
+        Err(ClientError::OtpRequired(ref e)) => {
+            assert_eq!(e.delivery, "sms");
+            assert_eq!(e.request.as_ref().unwrap().url, format!("{}events", server.url()));
+        }
+        other => panic!("expected an OtpRequiredError, got {:?}", other),
+    }
+}
+
+#[test]
+fn gives_other_unauthorized_responses_as_http_errors() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json(r#"{"message":"Bad credentials"}"#).status(401));
+    let client = server.client("glendc").with_basic_auth("glendc", "wrong", None);
+
+    match list_events(&client) {
+        Err(ClientError::Http(ref e)) => {
+            assert_eq!(e.code, ErrorStatus::Unauthorized);
+            assert_eq!(e.message.as_ref().map(|m| &m[..]), Some("Bad credentials"));
+        }
+        other => panic!("expected a 401, got {:?}", other),
+    }
+}
@@ -0,0 +1,73 @@
+extern crate github;
+
+use github::cache::MemoryCache;
+use github::http::Method;
+use github::mock::{MockResponse, MockServer};
+use github::activity::events::list_events;
+use github::activity::starring::{list_stargazers, list_stargazers_with_timestamps};
+
+/// A stargazer, which can be decoded both as a `User` and as a `Stargazer`.
+static STARGAZERS: &'static str = r#"[{"id":1,"login":"glendc","starred_at":"2015-01-01T15:00:00Z","user":{"id":1,"login":"glendc"}}]"#;
+
+#[test]
+fn reuses_the_cached_body_when_not_modified() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]").etag("\"abc\""));
+    let client = server.client("glendc").with_cache(MemoryCache::new());
+
+    let (_, resp) = list_events(&client).unwrap();
+    assert!(!resp.cached);
+    let (events, resp) = list_events(&client).unwrap();
+    assert!(events.is_empty());
+    assert!(resp.cached);
+    assert_eq!(resp.rate.remaining, 4999);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].header("If-None-Match"), None);
+    assert_eq!(requests[1].header("If-None-Match"), Some("\"abc\""));
+}
+
+#[test]
+fn sends_if_modified_since() {
+    let server = MockServer::start().unwrap();
+    let last_modified = "Thu, 01 Jan 2015 15:00:00 GMT";
+    server.route(Method::Get, "/events", MockResponse::json("[]").last_modified(last_modified));
+    let client = server.client("glendc").with_cache(MemoryCache::new());
+
+    list_events(&client).unwrap();
+    let (_, resp) = list_events(&client).unwrap();
+    assert!(resp.cached);
+    assert_eq!(server.requests()[1].header("If-Modified-Since"), Some(last_modified));
+}
+
+#[test]
+fn caches_per_media_type() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/repos/glendc/github-rust/stargazers", MockResponse::json(STARGAZERS).etag("\"abc\""));
+    let client = server.client("glendc").with_cache(MemoryCache::new());
+
+    list_stargazers(&client, "glendc", "github-rust").unwrap();
+    let (stargazers, resp) = list_stargazers_with_timestamps(&client, "glendc", "github-rust").unwrap();
+    assert!(!resp.cached);
+    assert_eq!(stargazers[0].user.login, "glendc");
+    assert_eq!(server.requests()[1].header("If-None-Match"), None);
+
+    let (users, resp) = list_stargazers(&client, "glendc", "github-rust").unwrap();
+    assert!(resp.cached);
+    assert_eq!(users[0].login, "glendc");
+    let (stargazers, resp) = list_stargazers_with_timestamps(&client, "glendc", "github-rust").unwrap();
+    assert!(resp.cached);
+    assert_eq!(stargazers[0].starred_at.to_rfc3339(), "2015-01-01T15:00:00+00:00");
+}
+
+#[test]
+fn ignores_responses_without_validators() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc").with_cache(MemoryCache::new());
+
+    list_events(&client).unwrap();
+    let (_, resp) = list_events(&client).unwrap();
+    assert!(!resp.cached);
+    assert_eq!(server.requests()[1].header("If-None-Match"), None);
+}
@@ -0,0 +1,189 @@
+extern crate filetime;
+extern crate github;
+
+use github::cache::{Cache, CacheEntry, DiskCache};
+use github::http::Method;
+use github::mock::{MockResponse, MockServer};
+use github::activity::events::list_events;
+use github::activity::feeds::get_feed;
+
+use filetime::FileTime;
+
+use std::env;
+use std::fs;
+use std::thread;
+use std::sync::Arc;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+
+/// A fresh directory for the cache of a single test.
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("github-rust-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn entry(body: &str) -> CacheEntry {
+    let mut headers = HashMap::new();
+    headers.insert("etag".to_string(), vec![format!("\"{}\"", body.len())]);
+    CacheEntry::new(&headers, body.as_bytes()).unwrap()
+}
+
+/// The names of all files in the directory of a cache.
+fn files(dir: &PathBuf) -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(dir).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn survives_a_restart() {
+    let dir = cache_dir("restart");
+    DiskCache::open(&dir).unwrap().set("a", entry("[1]"));
+
+    let cache = DiskCache::open(&dir).unwrap();
+    let cached = cache.get("a").unwrap();
+    assert_eq!(cached.body, b"[1]");
+    assert_eq!(cached.etag, Some("\"3\"".to_string()));
+    assert!(cache.get("b").is_none());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn evicts_the_least_recently_used_entries() {
+    let dir = cache_dir("evict");
+    let cache = DiskCache::open(&dir).unwrap().max_entries(2);
+    cache.set("a", entry("[1]"));
+    thread::sleep(Duration::from_millis(20));
+    cache.set("b", entry("[2]"));
+    thread::sleep(Duration::from_millis(20));
+    // Reading an entry marks it as the most recently used one.
+    assert!(cache.get("a").is_some());
+    thread::sleep(Duration::from_millis(20));
+    cache.set("c", entry("[3]"));
+
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("c").is_some());
+    assert_eq!(files(&dir).len(), 2);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn evicts_entries_exceeding_the_size_limit() {
+    let dir = cache_dir("size");
+    let cache = DiskCache::open(&dir).unwrap().max_bytes(1024);
+    cache.set("a", entry(&"x".repeat(2048)));
+    assert!(cache.get("a").is_none());
+    assert!(files(&dir).is_empty());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn removes_stale_temporary_files() {
+    let dir = cache_dir("tmp");
+    let cache = DiskCache::open(&dir).unwrap().max_bytes(1024);
+    // Temporary files as left behind by a crashed writer, an hour ago, and by one still writing.
+    let stale = dir.join("0123456789abcdef.1-0.tmp");
+    let fresh = dir.join("0123456789abcdef.2-0.tmp");
+    fs::write(&stale, "x".repeat(2048)).unwrap();
+    fs::write(&fresh, "x".repeat(2048)).unwrap();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let hour_ago = FileTime::from_seconds_since_1970(now - 60 * 60, 0);
+    filetime::set_file_times(&stale, hour_ago, hour_ago).unwrap();
+
+    // Temporary files don't count towards the size of the cache.
+    cache.set("a", entry("[1]"));
+    assert!(cache.get("a").is_some());
+    assert!(!stale.exists());
+    assert!(fresh.exists());
+    assert_eq!(files(&dir).len(), 2);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn reading_doesnt_rewrite_an_entry() {
+    let dir = cache_dir("touch");
+    let cache = DiskCache::open(&dir).unwrap();
+    cache.set("a", entry("[1]"));
+    let path = dir.join(&files(&dir)[0]);
+    let written = fs::metadata(&path).unwrap();
+    thread::sleep(Duration::from_millis(20));
+
+    assert!(cache.get("a").is_some());
+    let read = fs::metadata(&path).unwrap();
+    assert!(read.modified().unwrap() > written.modified().unwrap());
+    assert_eq!(files(&dir).len(), 1);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn concurrent_writers_never_corrupt_an_entry() {
+    let dir = Arc::new(cache_dir("concurrent"));
+    // Each writer has its own `DiskCache`, as if it were a separate process.
+    let writers: Vec<_> = (0..8).map(|writer| {
+        let dir = dir.clone();
+        thread::spawn(move || {
+            let cache = DiskCache::open(&*dir).unwrap();
+            let body = format!("[{}]", writer.to_string().repeat(64 * 1024));
+            for _ in 0..10 {
+                cache.set("shared", entry(&body));
+                if let Some(cached) = cache.get("shared") {
+                    let body = String::from_utf8(cached.body).unwrap();
+                    assert_eq!(body.len(), 64 * 1024 + 2);
+                    assert_eq!(cached.etag, Some(format!("\"{}\"", body.len())));
+                }
+            }
+        })
+    }).collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    // Only the entry is left, without any temporary files.
+    assert_eq!(files(&dir).len(), 1);
+    assert!(files(&dir)[0].ends_with(".json"));
+    let _ = fs::remove_dir_all(&*dir);
+}
+
+#[test]
+fn makes_conditional_requests() {
+    let dir = cache_dir("client");
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]").etag("\"abc\""));
+
+    let client = server.client("glendc").with_cache(DiskCache::open(&dir).unwrap());
+    list_events(&client).unwrap();
+    // A new client, as if the process was restarted.
+    let client = server.client("glendc").with_cache(DiskCache::open(&dir).unwrap());
+    let (_, resp) = list_events(&client).unwrap();
+    assert!(resp.cached);
+    assert_eq!(server.requests()[1].header("If-None-Match"), Some("\"abc\""));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn keeps_credentials_in_urls_out_of_the_cache() {
+    let dir = cache_dir("credentials");
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/glendc.private.atom", MockResponse::json(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>tag:github.com,2008:/glendc</id><title>glendc</title><updated>2015-01-01T15:00:00Z</updated></feed>"#)
+        .etag("\"v1\""));
+    let client = server.client("glendc").with_cache(DiskCache::open(&dir).unwrap());
+
+    let url = format!("{}glendc.private.atom?token=abc123", server.url());
+    get_feed(&client, &url).unwrap();
+    let (_, resp) = get_feed(&client, &url).unwrap();
+    assert!(resp.cached);
+
+    assert_eq!(files(&dir).len(), 1);
+    for file in files(&dir) {
+        let stored = fs::read_to_string(dir.join(&file)).unwrap();
+        assert!(!stored.contains("abc123"), "{}", stored);
+        assert!(stored.contains("token=REDACTED"));
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,142 @@
+extern crate github;
+
+use github::error::{ClientError, ErrorStatus};
+use github::http::Method;
+use github::mock::{events_json, issue_event_json, MockResponse, MockServer};
+use github::activity::{EventKind, EventReturnType};
+use github::activity::events::*;
+
+/// Check that a list request was sent to the given path and decoded the single event.
+fn assert_listed(server: &MockServer, result: EventReturnType, path: &str) {
+    let (events, _) = result.unwrap();
+    assert_eq!(server.requests().last().unwrap().path, path);
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.id, "2489651045");
+    assert_eq!(event.actor.login, "glendc");
+    assert_eq!(event.repo.name, "glendc/github-rust");
+    assert_eq!(event.created_at.to_rfc3339(), "2015-01-01T15:00:00+00:00");
+    match event.kind {
+        EventKind::Watch(ref payload) => assert_eq!(payload.action, "started"),
+        ref kind => panic!("expected a WatchEvent, got {}", kind.name()),
+    }
+}
+
+#[test]
+fn lists_events() {
+    let server = MockServer::start().unwrap();
+    let routes = [
+        "/events",
+        "/repos/glendc/github-rust/events",
+        "/users/glendc/received_events",
+        "/users/glendc/received_events/public",
+        "/users/glendc/events",
+        "/users/glendc/events/public",
+        "/networks/glendc/github-rust/events",
+        "/orgs/rust-lang/events",
+        "/users/glendc/events/orgs/rust-lang",
+    ];
+    for route in routes.iter() {
+        server.route(Method::Get, route, MockResponse::json(&events_json(&[2489651045])));
+    }
+    let client = &server.client("glendc");
+
+    assert_listed(&server, list_events(client), "/events");
+    assert_listed(&server, list_repo_events(client, "glendc", "github-rust"), "/repos/glendc/github-rust/events");
+    assert_listed(&server, list_my_repo_events(client, "github-rust"), "/repos/glendc/github-rust/events");
+    assert_listed(&server, list_received_user_events(client, "glendc"), "/users/glendc/received_events");
+    assert_listed(&server, list_my_received_events(client), "/users/glendc/received_events");
+    assert_listed(&server, list_received_public_user_events(client, "glendc"), "/users/glendc/received_events/public");
+    assert_listed(&server, list_my_received_public_events(client), "/users/glendc/received_events/public");
+    assert_listed(&server, list_user_events(client, "glendc"), "/users/glendc/events");
+    assert_listed(&server, list_my_events(client), "/users/glendc/events");
+    assert_listed(&server, list_public_user_events(client, "glendc"), "/users/glendc/events/public");
+    assert_listed(&server, list_my_public_user_events(client), "/users/glendc/events/public");
+    assert_listed(&server, list_public_network_repo_events(client, "glendc", "github-rust"), "/networks/glendc/github-rust/events");
+    assert_listed(&server, list_my_public_network_repo_events(client, "github-rust"), "/networks/glendc/github-rust/events");
+    assert_listed(&server, list_public_organisation_events(client, "rust-lang"), "/orgs/rust-lang/events");
+    assert_listed(&server, list_organisation_events(client, "glendc", "rust-lang"), "/users/glendc/events/orgs/rust-lang");
+    assert_listed(&server, list_my_organisation_events(client, "rust-lang"), "/users/glendc/events/orgs/rust-lang");
+}
+
+#[test]
+fn lists_issue_events() {
+    let server = MockServer::start().unwrap();
+    let issue_events = format!("[{}]", issue_event_json(1));
+    server.route(Method::Get, "/repos/glendc/github-rust/issues/events", MockResponse::json(&issue_events));
+    server.route(Method::Get, "/repos/glendc/github-rust/issues/42/events", MockResponse::json(&issue_events));
+    server.route(Method::Get, "/repos/glendc/github-rust/issues/events/1", MockResponse::json(&issue_event_json(1)));
+    let client = &server.client("glendc");
+
+    for result in vec![list_repo_issue_events(client, "glendc", "github-rust"),
+                       list_my_repo_issue_events(client, "github-rust"),
+                       list_issue_events(client, "glendc", "github-rust", 42),
+                       list_my_issue_events(client, "github-rust", 42)] {
+        let (events, _) = result.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, 1);
+    }
+    for result in vec![get_issue_event(client, "glendc", "github-rust", 1),
+                       get_my_issue_event(client, "github-rust", 1)] {
+        let (event, _) = result.unwrap();
+        assert_eq!(event.actor.unwrap().login, "glendc");
+        let rename = event.rename.unwrap();
+        assert_eq!(rename.from, "Add events");
+        assert_eq!(rename.to, "Add the Events API");
+    }
+    let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(paths, vec![
+        "/repos/glendc/github-rust/issues/events",
+        "/repos/glendc/github-rust/issues/events",
+        "/repos/glendc/github-rust/issues/42/events",
+        "/repos/glendc/github-rust/issues/42/events",
+        "/repos/glendc/github-rust/issues/events/1",
+        "/repos/glendc/github-rust/issues/events/1",
+    ]);
+}
+
+#[test]
+fn sends_the_default_headers() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc");
+
+    let (events, _) = list_events(&client).unwrap();
+    assert!(events.is_empty());
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.header("User-Agent"), Some("glendc"));
+    assert_eq!(request.header("Accept"), Some("application/vnd.github.v3+json"));
+    assert_eq!(request.header("Authorization"), None);
+}
+
+#[test]
+fn fails_for_an_unknown_route() {
+    let server = MockServer::start().unwrap();
+    let client = server.client("glendc");
+
+    match list_repo_events(&client, "glendc", "unknown") {
+        Err(ClientError::Http(e)) => {
+            assert_eq!(e.code, ErrorStatus::NotFound);
+            assert_eq!(e.message.as_ref().map(|msg| &msg[..]), Some("Not Found"));
+            let request = e.request.unwrap();
+            assert_eq!(request.method, Method::Get);
+            assert_eq!(request.url, format!("{}repos/glendc/unknown/events", server.url()));
+        }
+        other => panic!("expected a request error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+}
+
+#[test]
+fn populates_the_rate_limit() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json(&events_json(&[1])).rate_limit(60, 42, 1420124400));
+    let client = server.client("glendc");
+
+    let (_, resp) = list_events(&client).unwrap();
+    assert_eq!(resp.rate.limit, 60);
+    assert_eq!(resp.rate.remaining, 42);
+    assert_eq!(resp.rate.reset.timestamp(), 1420124400);
+    assert!(!resp.cached);
+    assert_eq!(resp.attempts, 1);
+}
@@ -0,0 +1,156 @@
+extern crate github;
+
+use github::http::Method;
+use github::mock::{MockResponse, MockServer};
+use github::activity::feeds::*;
+
+/// The timeline of a user, in the format given by github.com.
+static TIMELINE: &'static str = include_str!("data/timeline.atom");
+
+/// The feeds listed for an authenticated user, pointing to the given host for the private feeds.
+fn feeds(host: &str) -> String {
+    format!(r#"{{
+        "timeline_url": "https://github.com/timeline",
+        "user_url": "https://github.com/{{user}}",
+        "current_user_public_url": "https://github.com/glendc",
+        "current_user_url": "{}glendc.private.atom?token=abc123",
+        "current_user_actor_url": "https://github.com/glendc.private.actor?token=abc123",
+        "current_user_organization_url": "",
+        "current_user_organization_urls": []
+    }}"#, host)
+}
+
+fn atom(body: &str) -> MockResponse {
+    MockResponse::json(body).header("Content-Type", "application/atom+xml; charset=utf-8")
+}
+
+#[test]
+fn parses_a_github_timeline() {
+    let feed = parse_feed(TIMELINE).unwrap();
+    assert_eq!(feed.id, "tag:github.com,2008:/glendc");
+    assert_eq!(feed.title, "glendc’s Activity");
+    assert_eq!(feed.url, Some("https://github.com/glendc".to_string()));
+    assert_eq!(feed.updated.to_rfc3339(), "2015-01-01T15:00:00+00:00");
+    assert_eq!(feed.entries.len(), 2);
+
+    let push = &feed.entries[0];
+    assert_eq!(push.event_name(), Some("PushEvent"));
+    assert_eq!(push.title, "glendc pushed to master in glendc/github-rust");
+    assert_eq!(push.url, Some("https://github.com/glendc/github-rust/compare/a1b2c3d...6dcb09b".to_string()));
+    let author = push.author.as_ref().unwrap();
+    assert_eq!(author.name, "glendc");
+    assert_eq!(author.email, Some("glen@example.com".to_string()));
+    assert_eq!(push.thumbnail, Some("https://avatars.githubusercontent.com/u/1?s=30&v=4".to_string()));
+    assert_eq!(push.content, Some(r#"<div class="push"><a href="/glendc">glendc</a> pushed to master</div>"#.to_string()));
+
+    let watch = &feed.entries[1];
+    assert_eq!(watch.event_name(), Some("WatchEvent"));
+    assert_eq!(watch.updated.to_rfc3339(), "2015-01-01T13:00:00+00:00");
+    assert!(watch.author.as_ref().unwrap().email.is_none());
+    assert!(watch.thumbnail.is_none());
+    assert_eq!(watch.content, Some(r#"<div class="watch_started">starred <a href="/rust-lang/rust">rust-lang/rust</a></div>"#.to_string()));
+}
+
+#[test]
+fn replaces_entities() {
+    let feed = parse_feed(r#"<feed>
+        <id>1</id>
+        <title>&lt;b&gt;Tom &amp; Jerry&apos;s &quot;feed&quot;&lt;/b&gt; &#169; &#x2603;</title>
+        <updated>2015-01-01T15:00:00Z</updated>
+    </feed>"#).unwrap();
+    assert_eq!(feed.title, "<b>Tom & Jerry's \"feed\"</b> © ☃");
+    assert!(feed.entries.is_empty());
+    assert!(feed.url.is_none());
+}
+
+#[test]
+fn gives_the_name_of_an_event() {
+    let mut entry = parse_feed(TIMELINE).unwrap().entries.remove(0);
+    entry.id = "tag:github.com,2008:IssuesEvent/1234567890".to_string();
+    assert_eq!(entry.event_name(), Some("IssuesEvent"));
+    entry.id = "tag:github.com,2008:".to_string();
+    assert_eq!(entry.event_name(), None);
+    entry.id = "1234567890".to_string();
+    assert_eq!(entry.event_name(), None);
+}
+
+#[test]
+fn rejects_malformed_feeds() {
+    let malformed = [
+        "",
+        "not xml",
+        "<feed><id>1</id>",
+        "<feed><id>1</title></feed>",
+        "<feed><title a=b>x</title></feed>",
+        "<feed><title>&nbsp;</title></feed>",
+        "<feed><title>&amp</title></feed>",
+        "<feed><!-- unterminated comment </feed>",
+        "<feed><title><![CDATA[unterminated</title></feed>",
+    ];
+    for raw in malformed.iter() {
+        assert!(parse_feed(raw).is_err(), "parsed {:?}", raw);
+    }
+
+    let err = parse_feed("<feed><id>1</title></feed>").unwrap_err();
+    assert_eq!(err.position, Some(18));
+    assert_eq!(err.to_string(), "Invalid feed: expected </id> at byte 18");
+}
+
+#[test]
+fn rejects_invalid_atom() {
+    let err = parse_feed("<rss><channel/></rss>").unwrap_err();
+    assert_eq!(err.msg, "expected a feed element, found rss");
+    let err = parse_feed("<feed><id>1</id><updated>2015-01-01T15:00:00Z</updated></feed>").unwrap_err();
+    assert_eq!(err.msg, "missing title element in feed");
+    let err = parse_feed("<feed><id>1</id><title>x</title><updated>yesterday</updated></feed>").unwrap_err();
+    assert!(err.msg.starts_with("invalid timestamp yesterday"));
+}
+
+#[test]
+fn gets_a_feed_from_the_api() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/feeds", MockResponse::json(&feeds(&server.url())));
+    server.route(Method::Get, "/glendc.private.atom", atom(TIMELINE));
+    let client = server.client("glendc").with_token("secret");
+
+    let (feeds, _) = get_feeds(&client).unwrap();
+    assert_eq!(feeds.user_url_for("glendc"), "https://github.com/glendc");
+    let (feed, _) = get_feed(&client, "glendc.private.atom").unwrap();
+    assert_eq!(feed.entries.len(), 2);
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.header("Accept"), Some("application/atom+xml"));
+    assert_eq!(request.header("Authorization"), Some("token secret"));
+}
+
+#[test]
+fn sends_no_credentials_to_other_hosts() {
+    let api = MockServer::start().unwrap();
+    let github = MockServer::start().unwrap();
+    api.route(Method::Get, "/feeds", MockResponse::json(&feeds(&github.url())));
+    github.route(Method::Get, "/glendc.private.atom", atom(TIMELINE));
+    let client = api.client("glendc").with_token("secret");
+
+    let (feeds, _) = get_feeds(&client).unwrap();
+    let (feed, _) = get_feed(&client, feeds.current_user_url.as_ref().unwrap()).unwrap();
+    assert_eq!(feed.entries.len(), 2);
+
+    let request = github.requests().pop().unwrap();
+    assert_eq!(request.path, "/glendc.private.atom?token=abc123");
+    assert_eq!(request.header("Authorization"), None);
+    assert_eq!(api.requests()[0].header("Authorization"), Some("token secret"));
+}
+
+#[test]
+fn keeps_the_token_of_a_private_feed_out_of_errors() {
+    let github = MockServer::start().unwrap();
+    github.route(Method::Get, "/glendc.private.atom", MockResponse::json(r#"{"message":"Not Found"}"#).status(404));
+    github.route(Method::Get, "/glendc.private.actor", atom("<html></html>"));
+    let client = github.client("glendc");
+
+    for path in &["glendc.private.atom", "glendc.private.actor"] {
+        let err = get_feed(&client, &format!("{}{}?token=abc123", github.url(), path)).unwrap_err();
+        assert!(!err.to_string().contains("abc123"), "{}", err);
+        assert_eq!(err.request().unwrap().url, format!("{}{}?token=REDACTED", github.url(), path));
+    }
+}
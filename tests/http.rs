@@ -0,0 +1,63 @@
+extern crate github;
+
+use github::cache::MemoryCache;
+use github::http::{self, Method};
+use github::mock::{MockResponse, MockServer};
+
+/// The values of a header of the last request received by the server.
+fn header_values(server: &MockServer, name: &str) -> Vec<String> {
+    let request = server.requests().pop().unwrap();
+    request.headers.get(name).cloned().unwrap_or_default()
+}
+
+/// Get the events at the server, sending the given header options.
+fn get_events(server: &MockServer, client: &github::Client, opts: Vec<(&str, &str)>) {
+    let _: (Vec<u64>, _) = http::get(client, &format!("{}events", server.url()), Some(opts)).unwrap();
+}
+
+#[test]
+fn replaces_default_headers_regardless_of_case() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc");
+
+    get_events(&server, &client, vec![("accept", "application/vnd.github.v3.raw"), ("User-Agent", "github-rust-tests")]);
+    assert_eq!(header_values(&server, "accept"), vec!["application/vnd.github.v3.raw"]);
+    assert_eq!(header_values(&server, "user-agent"), vec!["github-rust-tests"]);
+}
+
+#[test]
+fn appends_other_headers() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let client = server.client("glendc");
+
+    get_events(&server, &client, vec![("X-GitHub-Api-Version", "2022-11-28")]);
+    assert_eq!(header_values(&server, "x-github-api-version"), vec!["2022-11-28"]);
+    assert_eq!(header_values(&server, "accept"), vec!["application/vnd.github.v3+json"]);
+    assert_eq!(header_values(&server, "user-agent"), vec!["glendc"]);
+}
+
+#[test]
+fn keeps_conditional_headers_given_as_options() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]").etag("\"v1\""));
+    let client = server.client("glendc").with_cache(MemoryCache::new());
+
+    get_events(&server, &client, vec![]);
+    assert!(header_values(&server, "if-none-match").is_empty());
+    get_events(&server, &client, vec![]);
+    assert_eq!(header_values(&server, "if-none-match"), vec!["\"v1\""]);
+    get_events(&server, &client, vec![("If-None-Match", "\"v0\"")]);
+    assert_eq!(header_values(&server, "if-none-match"), vec!["\"v0\""]);
+}
+
+#[test]
+fn redacts_credentials_in_urls() {
+    assert_eq!(http::redact_url("https://github.com/glendc.private.atom?token=abc123"),
+        "https://github.com/glendc.private.atom?token=REDACTED");
+    assert_eq!(http::redact_url("https://api.github.com/events?page=2&access_token=abc123&per_page=10"),
+        "https://api.github.com/events?page=2&access_token=REDACTED&per_page=10");
+    assert_eq!(http::redact_url("https://api.github.com/search?tokens=2"), "https://api.github.com/search?tokens=2");
+    assert_eq!(http::redact_url("https://api.github.com/events"), "https://api.github.com/events");
+}
@@ -0,0 +1,158 @@
+extern crate chrono;
+extern crate github;
+
+use github::error::{ClientError, ErrorStatus};
+use github::http::Method;
+use github::mock::{MockResponse, MockServer};
+use github::activity::notifications::*;
+
+use chrono::{TimeZone, UTC};
+
+/// A single notification thread, as given by the GitHub Notifications API.
+static THREAD: &'static str = r#"{
+    "id": "1",
+    "repository": {
+        "id": 42,
+        "name": "github-rust",
+        "full_name": "glendc/github-rust",
+        "url": "https://api.github.com/repos/glendc/github-rust"
+    },
+    "subject": {
+        "title": "Add the Notifications API",
+        "url": "https://api.github.com/repos/glendc/github-rust/issues/3",
+        "latest_comment_url": null,
+        "type": "Issue"
+    },
+    "reason": "review_requested",
+    "unread": true,
+    "updated_at": "2015-01-01T15:00:00Z",
+    "last_read_at": null,
+    "url": "https://api.github.com/notifications/threads/1"
+}"#;
+
+/// A subscription to a single notification thread.
+static SUBSCRIPTION: &'static str = r#"{
+    "subscribed": true,
+    "ignored": false,
+    "reason": null,
+    "created_at": "2015-01-01T15:00:00Z",
+    "url": "https://api.github.com/notifications/threads/1/subscription",
+    "thread_url": "https://api.github.com/notifications/threads/1"
+}"#;
+
+fn body(server: &MockServer) -> String {
+    String::from_utf8(server.requests().last().unwrap().body.clone()).unwrap()
+}
+
+#[test]
+fn lists_notifications() {
+    let server = MockServer::start().unwrap();
+    let threads = format!("[{}]", THREAD);
+    server.route(Method::Get, "/notifications", MockResponse::json(&threads));
+    server.route(Method::Get, "/repos/glendc/github-rust/notifications", MockResponse::json(&threads));
+    let client = server.client("glendc");
+
+    let (threads, _) = list_notifications(&client, &NotificationFilter::new()).unwrap();
+    assert_eq!(server.requests().last().unwrap().path, "/notifications");
+    let thread = &threads[0];
+    assert_eq!(thread.id, "1");
+    assert_eq!(thread.repository.name, "github-rust");
+    assert_eq!(thread.subject.kind, "Issue");
+    assert_eq!(thread.reason, NotificationReason::ReviewRequested);
+    assert!(thread.unread);
+    assert_eq!(thread.updated_at, UTC.ymd(2015, 1, 1).and_hms(15, 0, 0));
+    assert!(thread.last_read_at.is_none());
+
+    let (threads, _) = list_my_repo_notifications(&client, "github-rust", &NotificationFilter::new()).unwrap();
+    assert_eq!(server.requests().last().unwrap().path, "/repos/glendc/github-rust/notifications");
+    assert_eq!(threads.len(), 1);
+}
+
+#[test]
+fn lists_notifications_using_a_filter() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/notifications", MockResponse::json("[]"));
+    let client = server.client("glendc");
+
+    let filter = NotificationFilter::new()
+        .all()
+        .participating()
+        .since(UTC.ymd(2015, 1, 1).and_hms(15, 0, 0))
+        .before(UTC.ymd(2015, 2, 1).and_hms(0, 0, 0));
+    list_notifications(&client, &filter).unwrap();
+    assert_eq!(server.requests().last().unwrap().path,
+        "/notifications?all=true&participating=true&since=2015-01-01T15:00:00Z&before=2015-02-01T00:00:00Z");
+}
+
+#[test]
+fn marks_notifications_as_read() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Put, "/notifications", MockResponse::no_content().status(205));
+    server.route(Method::Put, "/repos/glendc/github-rust/notifications",
+        MockResponse::json(r#"{"message":"Unread notifications couldn't be marked in a single request."}"#).status(202));
+    let client = server.client("glendc");
+
+    mark_notifications_as_read(&client, None).unwrap();
+    assert_eq!(body(&server), "{}");
+
+    let last_read_at = UTC.ymd(2015, 1, 1).and_hms(15, 0, 0);
+    mark_repo_notifications_as_read(&client, "glendc", "github-rust", Some(last_read_at)).unwrap();
+    assert_eq!(body(&server), r#"{"last_read_at":"2015-01-01T15:00:00Z"}"#);
+}
+
+#[test]
+fn marks_notifications_as_read_whatever_the_accepted_body() {
+    let server = MockServer::start().unwrap();
+    let client = server.client("glendc");
+    for accepted in &[r#"{"message":{"text":"Still processing"},"queued":true,"count":3}"#, "null", "Accepted"] {
+        server.route(Method::Put, "/notifications", MockResponse::json(accepted).status(202));
+        let resp = mark_notifications_as_read(&client, None).unwrap();
+        assert!(!resp.cached);
+    }
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn manages_a_thread() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/notifications/threads/1", MockResponse::json(THREAD));
+    server.route(Method::Patch, "/notifications/threads/1", MockResponse::no_content().status(205));
+    let client = server.client("glendc");
+
+    let (thread, _) = get_thread(&client, "1").unwrap();
+    assert_eq!(thread.subject.title, "Add the Notifications API");
+    mark_thread_as_read(&client, "1").unwrap();
+    assert_eq!(server.requests().last().unwrap().method, "PATCH");
+}
+
+#[test]
+fn manages_a_thread_subscription() {
+    let server = MockServer::start().unwrap();
+    let path = "/notifications/threads/1/subscription";
+    server.route(Method::Get, path, MockResponse::json(SUBSCRIPTION));
+    server.route(Method::Put, path, MockResponse::json(SUBSCRIPTION));
+    server.route(Method::Delete, path, MockResponse::no_content());
+    let client = server.client("glendc");
+
+    let (subscription, _) = get_thread_subscription(&client, "1").unwrap();
+    assert!(subscription.subscribed);
+    assert!(!subscription.ignored);
+    assert_eq!(subscription.created_at, Some(UTC.ymd(2015, 1, 1).and_hms(15, 0, 0)));
+
+    set_thread_subscription(&client, "1", true).unwrap();
+    assert_eq!(body(&server), r#"{"ignored":true}"#);
+
+    delete_thread_subscription(&client, "1").unwrap();
+    assert_eq!(server.requests().last().unwrap().method, "DELETE");
+}
+
+#[test]
+fn gives_a_missing_thread_as_not_found() {
+    let server = MockServer::start().unwrap();
+    let client = server.client("glendc");
+
+    match get_thread(&client, "2") {
+        Err(ClientError::Http(ref e)) => assert_eq!(e.code, ErrorStatus::NotFound),
+        other => panic!("expected a 404, got {:?}", other.map(|(thread, _)| thread)),
+    }
+}
@@ -0,0 +1,109 @@
+extern crate github;
+
+use github::http::Method;
+use github::mock::{events_json, MockResponse, MockServer};
+use github::pagination::Paginator;
+use github::activity::EventResponse;
+use github::activity::events::list_events;
+use github::activity::starring::list_stargazers_with_timestamps;
+
+fn stargazer(login: &str) -> String {
+    format!(r#"{{"starred_at":"2015-06-01T12:00:00Z","user":{{"id":1,"login":"{}"}}}}"#, login)
+}
+
+#[test]
+fn follows_the_next_links() {
+    let server = MockServer::start().unwrap();
+    let page2 = format!("{}events?page=2", server.url());
+    server.route(Method::Get, "/events?page=2", MockResponse::json(&events_json(&[3])));
+    server.route(Method::Get, "/events", MockResponse::json(&events_json(&[1, 2]))
+        .link("next", &page2)
+        .link("last", &page2));
+    let client = server.client("glendc");
+
+    let ids: Vec<String> = Paginator::<EventResponse>::new(&client, &format!("{}events", server.url()))
+        .map(|event| event.unwrap().id)
+        .collect();
+    assert_eq!(ids, vec!["1", "2", "3"]);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn follows_cursor_links() {
+    let server = MockServer::start().unwrap();
+    let next = format!("{}events?after=Y3Vyc29y,Mg", server.url());
+    server.route(Method::Get, "/events?after=Y3Vyc29y,Mg", MockResponse::json(&events_json(&[2])));
+    server.route(Method::Get, "/events", MockResponse::json(&events_json(&[1])).link("next", &next));
+    let client = server.client("glendc");
+
+    let ids: Vec<String> = Paginator::from_page(&client, list_events(&client))
+        .map(|event| event.unwrap().id)
+        .collect();
+    assert_eq!(ids, vec!["1", "2"]);
+    assert_eq!(server.requests()[1].path, "/events?after=Y3Vyc29y,Mg");
+}
+
+#[test]
+fn respects_the_limits() {
+    let server = MockServer::start().unwrap();
+    let page2 = format!("{}events?page=2", server.url());
+    server.route(Method::Get, "/events?page=2", MockResponse::json(&events_json(&[3])));
+    server.route(Method::Get, "/events", MockResponse::json(&events_json(&[1, 2])).link("next", &page2));
+    let client = server.client("glendc");
+
+    let items = Paginator::from_page(&client, list_events(&client)).max_items(1).count();
+    assert_eq!(items, 1);
+    let items = Paginator::from_page(&client, list_events(&client)).max_pages(1).count();
+    assert_eq!(items, 2);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn resends_the_headers_of_the_first_page() {
+    let server = MockServer::start().unwrap();
+    let page2 = format!("{}repos/glendc/github-rust/stargazers?page=2", server.url());
+    server.route(Method::Get, "/repos/glendc/github-rust/stargazers?page=2",
+                 MockResponse::json(&format!("[{}]", stargazer("octocat"))));
+    server.route(Method::Get, "/repos/glendc/github-rust/stargazers",
+                 MockResponse::json(&format!("[{}]", stargazer("glendc"))).link("next", &page2));
+    let client = server.client("glendc");
+
+    let logins: Vec<String> = Paginator::from_page(&client, list_stargazers_with_timestamps(&client, "glendc", "github-rust"))
+        .map(|stargazer| stargazer.unwrap().user.login)
+        .collect();
+    assert_eq!(logins, vec!["glendc", "octocat"]);
+    for request in server.requests() {
+        assert_eq!(request.header("Accept"), Some("application/vnd.github.v3.star+json"));
+    }
+}
+
+#[test]
+fn sends_the_given_headers_with_every_page() {
+    let server = MockServer::start().unwrap();
+    let page2 = format!("{}events?page=2", server.url());
+    server.route(Method::Get, "/events?page=2", MockResponse::json(&events_json(&[2])));
+    server.route(Method::Get, "/events", MockResponse::json(&events_json(&[1])).link("next", &page2));
+    let client = server.client("glendc");
+
+    let items = Paginator::<EventResponse>::new(&client, &format!("{}events", server.url()))
+        .headers(vec![("Accept", "application/vnd.github.v3.full+json")])
+        .count();
+    assert_eq!(items, 2);
+    for request in server.requests() {
+        assert_eq!(request.header("Accept"), Some("application/vnd.github.v3.full+json"));
+    }
+}
+
+#[test]
+fn yields_the_error_last() {
+    let server = MockServer::start().unwrap();
+    let page2 = format!("{}events?page=2", server.url());
+    server.route(Method::Get, "/events?page=2", MockResponse::json(r#"{"message":"Server Error"}"#).status(500));
+    server.route(Method::Get, "/events", MockResponse::json(&events_json(&[1])).link("next", &page2));
+    let client = server.client("glendc");
+
+    let items: Vec<_> = Paginator::from_page(&client, list_events(&client)).collect();
+    assert_eq!(items.len(), 2);
+    assert!(items[0].is_ok());
+    assert!(items[1].is_err());
+}
@@ -0,0 +1,220 @@
+extern crate chrono;
+extern crate github;
+extern crate rustc_serialize;
+
+use github::activity::EventKind;
+
+use chrono::{TimeZone, UTC};
+
+use rustc_serialize::json::Json;
+
+/// A pull request, as found in the payloads of the GitHub Events API.
+static PULL_REQUEST: &'static str = r#"{
+    "id": 7,
+    "number": 3,
+    "title": "Add the Events API",
+    "user": { "id": 1, "login": "glendc" },
+    "state": "open",
+    "body": null,
+    "head": { "label": "glendc:events", "ref": "events", "sha": "6dcb09b", "repo": null },
+    "base": { "label": "glendc:master", "ref": "master", "sha": "a1b2c3d", "repo": null },
+    "url": "https://api.github.com/repos/glendc/github-rust/pulls/3",
+    "html_url": "https://github.com/glendc/github-rust/pull/3",
+    "created_at": "2015-01-01T15:00:00Z"
+}"#;
+
+fn kind(name: &str, payload: &str) -> EventKind {
+    EventKind::new(name, Json::from_str(payload).unwrap())
+}
+
+#[test]
+fn decodes_push_event() {
+    let payload = r#"{
+        "push_id": 536863970,
+        "size": 1,
+        "distinct_size": 1,
+        "ref": "refs/heads/master",
+        "head": "6dcb09b",
+        "before": "a1b2c3d",
+        "commits": [{
+            "sha": "6dcb09b",
+            "message": "Add the Events API",
+            "author": { "name": "Glen", "email": "glen@example.com" },
+            "distinct": true,
+            "url": "https://api.github.com/repos/glendc/github-rust/commits/6dcb09b"
+        }]
+    }"#;
+    match kind("PushEvent", payload) {
+        EventKind::Push(payload) => {
+            assert_eq!(payload.git_ref, "refs/heads/master");
+            assert_eq!(payload.size, Some(1));
+            assert_eq!(payload.distinct_size, Some(1));
+            assert_eq!(payload.commits.unwrap()[0].author.name, "Glen");
+        }
+        kind => panic!("expected a PushEvent, got {:?}", kind),
+    }
+}
+
+#[test]
+fn decodes_push_event_without_commits() {
+    // Recent events leave out the size and the commits of a push.
+    let payload = r#"{
+        "push_id": 536863970,
+        "ref": "refs/heads/master",
+        "head": "6dcb09b",
+        "before": "a1b2c3d"
+    }"#;
+    match kind("PushEvent", payload) {
+        EventKind::Push(payload) => {
+            assert_eq!(payload.head, "6dcb09b");
+            assert!(payload.size.is_none());
+            assert!(payload.distinct_size.is_none());
+            assert!(payload.commits.is_none());
+        }
+        kind => panic!("expected a PushEvent, got {:?}", kind),
+    }
+}
+
+#[test]
+fn decodes_pull_request_review_thread_event() {
+    let payload = format!(r#"{{
+        "action": "resolved",
+        "pull_request": {},
+        "thread": {{ "node_id": "PRRT_1", "comments": [] }}
+    }}"#, PULL_REQUEST);
+    let kind = kind("PullRequestReviewThreadEvent", &payload);
+    assert_eq!(kind.name(), "PullRequestReviewThreadEvent");
+    match kind {
+        EventKind::PullRequestReviewThread(payload) => {
+            assert_eq!(payload.action, "resolved");
+            assert_eq!(payload.pull_request.head.git_ref, "events");
+            assert_eq!(payload.pull_request.created_at, UTC.ymd(2015, 1, 1).and_hms(15, 0, 0));
+            assert!(payload.pull_request.merged_at.is_none());
+            assert_eq!(payload.thread.node_id, "PRRT_1");
+        }
+        kind => panic!("expected a PullRequestReviewThreadEvent, got {:?}", kind),
+    }
+}
+
+#[test]
+fn keeps_decode_errors_of_known_types() {
+    // The `ref` key of a push is required.
+    let kind = kind("PushEvent", r#"{ "push_id": 1, "head": "6dcb09b", "before": "a1b2c3d" }"#);
+    assert_eq!(kind.name(), "PushEvent");
+    match kind {
+        EventKind::Invalid(name, payload, e) => {
+            assert_eq!(name, "PushEvent");
+            assert_eq!(payload.find("push_id").and_then(|id| id.as_u64()), Some(1));
+            assert!(format!("{:?}", e).contains("ref"));
+        }
+        kind => panic!("expected an invalid PushEvent, got {:?}", kind),
+    }
+}
+
+#[test]
+fn keeps_unknown_types() {
+    let kind = kind("FutureEvent", r#"{ "action": "created" }"#);
+    assert_eq!(kind.name(), "FutureEvent");
+    match kind {
+        EventKind::Unknown(name, payload) => {
+            assert_eq!(name, "FutureEvent");
+            assert_eq!(payload.find("action").and_then(|a| a.as_string()), Some("created"));
+        }
+        kind => panic!("expected an unknown event, got {:?}", kind),
+    }
+}
+
+#[test]
+fn decodes_public_event_without_payload() {
+    match kind("PublicEvent", "{}") {
+        EventKind::Public => {}
+        kind => panic!("expected a PublicEvent, got {:?}", kind),
+    }
+}
+
+#[test]
+fn decodes_timestamps() {
+    let payload = r#"{
+        "action": "created",
+        "issue": {
+            "id": 1, "number": 3, "title": "Add the Events API", "user": { "id": 1, "login": "glendc" },
+            "state": "closed",
+            "url": "https://api.github.com/repos/glendc/github-rust/issues/3",
+            "html_url": "https://github.com/glendc/github-rust/issues/3",
+            "created_at": "2015-01-01T15:00:00Z",
+            "updated_at": "2015-01-02T15:00:00+01:00",
+            "closed_at": null
+        },
+        "comment": {
+            "id": 2, "body": "Done", "user": { "id": 1, "login": "glendc" },
+            "url": "https://api.github.com/repos/glendc/github-rust/issues/comments/2",
+            "html_url": "https://github.com/glendc/github-rust/issues/3#issuecomment-2",
+            "created_at": "2015-01-03T15:00:00Z"
+        }
+    }"#;
+    match kind("IssueCommentEvent", payload) {
+        EventKind::IssueComment(payload) => {
+            assert_eq!(payload.issue.created_at, UTC.ymd(2015, 1, 1).and_hms(15, 0, 0));
+            assert_eq!(payload.issue.updated_at, Some(UTC.ymd(2015, 1, 2).and_hms(14, 0, 0)));
+            assert!(payload.issue.closed_at.is_none());
+            assert_eq!(payload.comment.created_at, UTC.ymd(2015, 1, 3).and_hms(15, 0, 0));
+            assert!(payload.comment.updated_at.is_none());
+        }
+        kind => panic!("expected an IssueCommentEvent, got {:?}", kind),
+    }
+
+    let payload = r#"{
+        "action": "published",
+        "release": {
+            "id": 1, "tag_name": "v0.1.0", "draft": false, "prerelease": false,
+            "author": { "id": 1, "login": "glendc" },
+            "url": "https://api.github.com/repos/glendc/github-rust/releases/1",
+            "html_url": "https://github.com/glendc/github-rust/releases/v0.1.0",
+            "created_at": "2015-01-01T15:00:00Z",
+            "published_at": "2015-01-01T16:00:00Z"
+        }
+    }"#;
+    match kind("ReleaseEvent", payload) {
+        EventKind::Release(payload) => {
+            assert_eq!(payload.release.created_at, UTC.ymd(2015, 1, 1).and_hms(15, 0, 0));
+            assert_eq!(payload.release.published_at, Some(UTC.ymd(2015, 1, 1).and_hms(16, 0, 0)));
+        }
+        kind => panic!("expected a ReleaseEvent, got {:?}", kind),
+    }
+
+    let payload = format!(r#"{{
+        "action": "created",
+        "review": {{
+            "id": 1, "user": {{ "id": 1, "login": "glendc" }}, "state": "approved",
+            "html_url": "https://github.com/glendc/github-rust/pull/3#pullrequestreview-1",
+            "submitted_at": "2015-01-01T17:00:00Z"
+        }},
+        "pull_request": {}
+    }}"#, PULL_REQUEST);
+    match kind("PullRequestReviewEvent", &payload) {
+        EventKind::PullRequestReview(payload) => {
+            assert_eq!(payload.review.submitted_at, Some(UTC.ymd(2015, 1, 1).and_hms(17, 0, 0)));
+        }
+        kind => panic!("expected a PullRequestReviewEvent, got {:?}", kind),
+    }
+
+    match kind("SponsorshipEvent", r#"{ "action": "created", "effective_date": "2015-02-01T00:00:00Z" }"#) {
+        EventKind::Sponsorship(payload) => {
+            assert_eq!(payload.effective_date, Some(UTC.ymd(2015, 2, 1).and_hms(0, 0, 0)));
+        }
+        kind => panic!("expected a SponsorshipEvent, got {:?}", kind),
+    }
+}
+
+#[test]
+fn gives_invalid_timestamps_as_invalid_payloads() {
+    let payload = format!(r#"{{ "action": "opened", "number": 3, "pull_request": {} }}"#,
+        PULL_REQUEST.replace("2015-01-01T15:00:00Z", "yesterday"));
+    match kind("PullRequestEvent", &payload) {
+        EventKind::Invalid(name, _, e) => {
+            assert_eq!(name, "PullRequestEvent");
+            assert!(format!("{:?}", e).contains("yesterday"));
+        }
+        kind => panic!("expected an invalid PullRequestEvent, got {:?}", kind),
+    }
+}
@@ -0,0 +1,124 @@
+extern crate github;
+
+use github::cache::MemoryCache;
+use github::http::Method;
+use github::mock::{events_json, MockResponse, MockServer};
+use github::activity::poller::EventPoller;
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Respond with the events with the given ids, using the given ETag and a poll interval of 1 second.
+fn poll_response(ids: &[u64], etag: &str) -> MockResponse {
+    MockResponse::json(&events_json(ids)).etag(etag).header("X-Poll-Interval", "1")
+}
+
+#[test]
+fn yields_events_from_oldest_to_most_recent() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/repos/glendc/github-rust/events", poll_response(&[3, 2, 1], "\"v1\""));
+    let client = server.client("glendc");
+
+    let mut poller = EventPoller::for_repo(&client, "glendc", "github-rust").after(1);
+    let ids: Vec<String> = poller.by_ref().take(2).map(|event| event.unwrap().id).collect();
+    assert_eq!(ids, vec!["2", "3"]);
+    assert_eq!(poller.last_id(), Some(3));
+    assert_eq!(server.requests().len(), 1);
+}
+
+/// Poll until a new event is given, which is published two polls after the first one,
+/// so that the poll in between is answered with a `304 Not Modified`.
+fn polls_conditionally(server: Arc<MockServer>, poller: &mut EventPoller) {
+    assert_eq!(poller.next().unwrap().unwrap().id, "1");
+
+    let publisher = server.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(1500));
+        publisher.route(Method::Get, "/events", poll_response(&[2, 1], "\"v2\""));
+    });
+    let start = Instant::now();
+    assert_eq!(poller.next().unwrap().unwrap().id, "2");
+    assert!(start.elapsed() >= Duration::from_secs(2));
+    handle.join().unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].header("If-None-Match"), None);
+    assert_eq!(requests[1].header("If-None-Match"), Some("\"v1\""));
+    assert_eq!(requests[2].header("If-None-Match"), Some("\"v1\""));
+}
+
+#[test]
+fn polls_conditionally_without_a_cache() {
+    let server = Arc::new(MockServer::start().unwrap());
+    server.route(Method::Get, "/events", poll_response(&[1], "\"v1\""));
+    let client = server.client("glendc");
+    let mut poller = EventPoller::for_events(&client);
+    polls_conditionally(server.clone(), &mut poller);
+}
+
+#[test]
+fn polls_conditionally_with_a_cache() {
+    let server = Arc::new(MockServer::start().unwrap());
+    server.route(Method::Get, "/events", poll_response(&[1], "\"v1\""));
+    let client = server.client("glendc").with_cache(MemoryCache::new());
+    let mut poller = EventPoller::for_events(&client);
+    polls_conditionally(server.clone(), &mut poller);
+}
+
+#[test]
+fn gives_failed_polls_as_errors() {
+    let server = MockServer::start().unwrap();
+    let client = server.client("glendc");
+    let mut poller = EventPoller::for_public_organisation(&client, "rust-lang");
+    assert!(poller.next().unwrap().is_err());
+}
+
+#[test]
+fn polls_at_the_interval_advised_by_a_not_modified_response() {
+    let server = Arc::new(MockServer::start().unwrap());
+    server.route(Method::Get, "/events", poll_response(&[1], "\"v1\""));
+    let client = server.client("glendc");
+    let mut poller = EventPoller::for_events(&client);
+    assert_eq!(poller.next().unwrap().unwrap().id, "1");
+
+    // GitHub asks to slow down in the `304 Not Modified` response of the second poll.
+    server.route(Method::Get, "/events", MockResponse::json(&events_json(&[1]))
+        .etag("\"v1\"")
+        .header("X-Poll-Interval", "3"));
+    let publisher = server.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(1500));
+        publisher.route(Method::Get, "/events", poll_response(&[2, 1], "\"v2\""));
+    });
+    let start = Instant::now();
+    assert_eq!(poller.next().unwrap().unwrap().id, "2");
+    assert!(start.elapsed() >= Duration::from_secs(4));
+    handle.join().unwrap();
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn polls_the_endpoints_of_the_events_api() {
+    let server = MockServer::start().unwrap();
+    let client = server.client("glendc");
+    let pollers = vec![
+        (EventPoller::for_events(&client), "/events"),
+        (EventPoller::for_repo(&client, "glendc", "github-rust"), "/repos/glendc/github-rust/events"),
+        (EventPoller::for_received_user(&client, "glendc"), "/users/glendc/received_events"),
+        (EventPoller::for_received_public_user(&client, "glendc"), "/users/glendc/received_events/public"),
+        (EventPoller::for_user(&client, "glendc"), "/users/glendc/events"),
+        (EventPoller::for_public_user(&client, "glendc"), "/users/glendc/events/public"),
+        (EventPoller::for_public_network_repo(&client, "glendc", "github-rust"), "/networks/glendc/github-rust/events"),
+        (EventPoller::for_public_organisation(&client, "rust-lang"), "/orgs/rust-lang/events"),
+        (EventPoller::for_organisation(&client, "glendc", "rust-lang"), "/users/glendc/events/orgs/rust-lang"),
+    ];
+    let paths: Vec<&str> = pollers.iter().map(|&(_, path)| path).collect();
+    for (mut poller, _) in pollers {
+        // Nothing is routed, so each first poll fails right away.
+        assert!(poller.next().unwrap().is_err());
+    }
+    let requested: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(requested, paths);
+}
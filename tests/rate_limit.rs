@@ -0,0 +1,128 @@
+extern crate github;
+extern crate chrono;
+
+use github::error::{ClientError, ErrorStatus};
+use github::http::{self, Method};
+use github::mock::{MockResponse, MockServer};
+use github::rate_limit::{get_rate_limit, RateLimitPolicy};
+use github::activity::events::list_events;
+
+use chrono::UTC;
+
+use std::time::Instant;
+
+#[test]
+fn gets_the_rate_limit_of_each_resource() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/rate_limit", MockResponse::json(r#"{
+        "resources": {
+            "core": { "limit": 5000, "remaining": 4999, "reset": 1372700873 },
+            "search": { "limit": 30, "remaining": 18, "reset": 1372697452 },
+            "graphql": { "limit": 5000, "remaining": 4993, "reset": 1372700389 }
+        },
+        "rate": { "limit": 5000, "remaining": 4999, "reset": 1372700873 }
+    }"#));
+    let client = server.client("glendc");
+
+    let (limits, _) = get_rate_limit(&client).unwrap();
+    assert_eq!(limits.resources.core.remaining, 4999);
+    assert_eq!(limits.resources.search.limit, 30);
+    assert_eq!(limits.resources.search.reset.timestamp(), 1372697452);
+    assert_eq!(limits.resources.graphql.unwrap().remaining, 4993);
+    assert!(limits.resources.integration_manifest.is_none());
+    assert_eq!(limits.rate.limit, 5000);
+}
+
+#[test]
+fn fails_fast_once_the_limit_is_exhausted() {
+    let server = MockServer::start().unwrap();
+    let reset = UTC::now().timestamp() + 3600;
+    server.route(Method::Get, "/events", MockResponse::json("[]").rate_limit(60, 0, reset));
+    let client = server.client("glendc");
+
+    list_events(&client).unwrap();
+    match list_events(&client) {
+        Err(ClientError::RateLimited(e)) => {
+            assert_eq!(e.reset.timestamp(), reset);
+            assert!(e.request.unwrap().url.ends_with("/events"));
+        }
+        other => panic!("expected a rate limit error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+    // The second request was never sent.
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(client.rate_limiter.last_rate().unwrap().remaining, 0);
+}
+
+#[test]
+fn tracks_each_resource_separately() {
+    let server = MockServer::start().unwrap();
+    let reset = UTC::now().timestamp() + 3600;
+    server.route(Method::Get, "/search/repositories",
+                 MockResponse::json("{}").rate_limit(30, 0, reset).header("X-RateLimit-Resource", "search"));
+    server.route(Method::Get, "/events",
+                 MockResponse::json("[]").rate_limit(5000, 4999, reset).header("X-RateLimit-Resource", "core"));
+    let client = server.client("glendc");
+    let search = format!("{}search/repositories?q=github", server.url());
+
+    http::get_raw(&client, &search, None).unwrap();
+    // The exhausted search limit doesn't block the core requests.
+    list_events(&client).unwrap();
+    list_events(&client).unwrap();
+    match http::get_raw(&client, &search, None) {
+        Err(ClientError::RateLimited(e)) => assert_eq!(e.reset.timestamp(), reset),
+        other => panic!("expected a rate limit error, got {:?}", other.map(|(body, _)| body)),
+    }
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(client.rate_limiter.rate("search").unwrap().remaining, 0);
+    assert_eq!(client.rate_limiter.rate("core").unwrap().remaining, 4999);
+}
+
+#[test]
+fn fails_when_rejected_because_of_the_rate_limit() {
+    let server = MockServer::start().unwrap();
+    let reset = UTC::now().timestamp() + 3600;
+    server.route(Method::Get, "/events", MockResponse::json(r#"{"message":"API rate limit exceeded"}"#)
+        .status(403)
+        .rate_limit(60, 0, reset));
+    let client = server.client("glendc");
+
+    match list_events(&client) {
+        Err(ClientError::RateLimited(e)) => assert_eq!(e.reset.timestamp(), reset),
+        other => panic!("expected a rate limit error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+}
+
+#[test]
+fn ignores_values_out_of_range() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json(r#"{"message":"abuse detected"}"#)
+        .status(429)
+        .header("Retry-After", "9223372036854775807"));
+    server.route(Method::Get, "/users/glendc/events", MockResponse::json(r#"{"message":"API rate limit exceeded"}"#)
+        .status(403)
+        .rate_limit(60, 0, 9223372036854775807));
+    let client = server.client("glendc");
+
+    match list_events(&client) {
+        Err(ClientError::Http(e)) => assert_eq!(e.code, ErrorStatus::TooManyRequests),
+        other => panic!("expected a request error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+    match github::activity::events::list_my_events(&client) {
+        Err(ClientError::Http(e)) => assert_eq!(e.code, ErrorStatus::Forbidden),
+        other => panic!("expected a request error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+}
+
+#[test]
+fn waits_for_the_reset() {
+    let server = MockServer::start().unwrap();
+    let reset = UTC::now().timestamp() + 1;
+    server.route(Method::Get, "/events", MockResponse::json("[]").rate_limit(60, 0, reset));
+    let client = server.client("glendc").with_rate_limit_policy(RateLimitPolicy::Wait);
+
+    list_events(&client).unwrap();
+    let started = Instant::now();
+    list_events(&client).unwrap();
+    assert!(started.elapsed().as_secs() >= 1);
+    assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,136 @@
+extern crate github;
+
+use github::error::{ClientError, ErrorStatus};
+use github::http::{self, Method};
+use github::mock::{MockResponse, MockServer};
+use github::retry::RetryPolicy;
+use github::transport::{CurlTransport, RawResponse, Transport};
+use github::activity::events::list_events;
+
+use std::error::Error;
+use std::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// `Flaky` is a `Transport` failing the first `failures` requests it sends.
+struct Flaky {
+    failures: usize,
+    sent: Arc<AtomicUsize>,
+}
+
+impl Transport for Flaky {
+    fn send(&self, method: Method, url: &str, headers: &[(String, String)], body: Option<&[u8]>) -> Result<RawResponse, Box<Error + Send + Sync>> {
+        if self.sent.fetch_add(1, Ordering::SeqCst) < self.failures {
+            return Err(From::from("connection reset by peer"));
+        }
+        CurlTransport.send(method, url, headers, body)
+    }
+}
+
+fn policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::new(max_attempts, Duration::from_millis(10)).jitter(false)
+}
+
+#[test]
+fn retries_transport_failures() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let sent = Arc::new(AtomicUsize::new(0));
+    let client = server.client("glendc")
+        .with_transport(Flaky { failures: 2, sent: sent.clone() })
+        .with_retry_policy(policy(3));
+
+    let (_, resp) = list_events(&client).unwrap();
+    assert_eq!(resp.attempts, 3);
+    assert_eq!(sent.load(Ordering::SeqCst), 3);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn gives_up_after_the_last_attempt() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("[]"));
+    let sent = Arc::new(AtomicUsize::new(0));
+    let client = server.client("glendc")
+        .with_transport(Flaky { failures: 5, sent: sent.clone() })
+        .with_retry_policy(policy(3));
+
+    match list_events(&client) {
+        Err(ClientError::Transport(e)) => assert_eq!(e.cause.to_string(), "connection reset by peer"),
+        other => panic!("expected a transport error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+    assert_eq!(sent.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn retries_retryable_statuses() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json(r#"{"message":"Service Unavailable"}"#).status(503));
+    let client = server.client("glendc").with_retry_policy(policy(3));
+
+    match list_events(&client) {
+        Err(ClientError::Http(e)) => assert_eq!(e.code, ErrorStatus::ServerError(503)),
+        other => panic!("expected a request error, got {:?}", other.map(|(events, _)| events.len())),
+    }
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn doesnt_retry_other_statuses() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json(r#"{"message":"Bad credentials"}"#).status(401));
+    let client = server.client("glendc").with_retry_policy(policy(3));
+
+    assert!(list_events(&client).is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn doesnt_retry_non_idempotent_requests() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Post, "/events", MockResponse::json("{}").status(502));
+    let url = format!("{}events", server.url());
+
+    let client = server.client("glendc").with_retry_policy(policy(3));
+    assert!(http::post::<(), ()>(&client, &url, &()).is_err());
+    assert_eq!(server.requests().len(), 1);
+
+    let client = server.client("glendc").with_retry_policy(policy(3).retry_non_idempotent(true));
+    assert!(http::post::<(), ()>(&client, &url, &()).is_err());
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[test]
+fn never_retries_by_default() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/events", MockResponse::json("{}").status(503));
+    let client = server.client("glendc");
+
+    assert!(list_events(&client).is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn backs_off_exponentially() {
+    let policy = RetryPolicy::new(10, Duration::from_millis(100)).jitter(false).max_delay(Duration::from_secs(1));
+    let delays: Vec<Option<Duration>> = (1..6).map(|attempts| policy.backoff(Method::Get, attempts)).collect();
+    assert_eq!(delays, vec![
+        Some(Duration::from_millis(100)),
+        Some(Duration::from_millis(200)),
+        Some(Duration::from_millis(400)),
+        Some(Duration::from_millis(800)),
+        Some(Duration::from_secs(1)),
+    ]);
+    assert_eq!(policy.backoff(Method::Get, 10), None);
+    assert_eq!(policy.backoff(Method::Post, 1), None);
+}
+
+#[test]
+fn jitters_within_the_second_half() {
+    let policy = RetryPolicy::new(10, Duration::from_millis(100));
+    for attempts in 1..5 {
+        let full = Duration::from_millis(100 << (attempts - 1));
+        let delay = policy.backoff(Method::Get, attempts).unwrap();
+        assert!(delay >= full / 2 && delay <= full, "{:?} isn't within half of {:?}", delay, full);
+    }
+}
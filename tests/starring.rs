@@ -0,0 +1,93 @@
+extern crate github;
+
+use github::error::{ClientError, ErrorStatus};
+use github::http::Method;
+use github::mock::{MockResponse, MockServer};
+use github::activity::starring::*;
+
+/// A stargazer, which can be decoded both as a `User` and as a `Stargazer`.
+static STARGAZERS: &'static str = r#"[{
+    "id": 1,
+    "login": "glendc",
+    "starred_at": "2015-01-01T15:00:00Z",
+    "user": { "id": 1, "login": "glendc" }
+}]"#;
+
+/// A starred repository, which can be decoded both as a `Repository` and as a `StarredRepository`.
+static STARRED: &'static str = r#"[{
+    "id": 42,
+    "name": "github-rust",
+    "url": "https://api.github.com/repos/glendc/github-rust",
+    "starred_at": "2015-01-01T15:00:00Z",
+    "repo": {
+        "id": 42,
+        "name": "github-rust",
+        "url": "https://api.github.com/repos/glendc/github-rust"
+    }
+}]"#;
+
+/// The values of the Accept header of the last request received by the server.
+fn accept_headers(server: &MockServer) -> Vec<String> {
+    server.requests().last().unwrap().headers.get("accept").cloned().unwrap_or_default()
+}
+
+#[test]
+fn lists_stargazers() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/repos/glendc/github-rust/stargazers", MockResponse::json(STARGAZERS));
+    let client = server.client("glendc");
+
+    let (users, _) = list_my_stargazers(&client, "github-rust").unwrap();
+    assert_eq!(users[0].login, "glendc");
+    assert_eq!(accept_headers(&server), vec!["application/vnd.github.v3+json"]);
+
+    let (stargazers, _) = list_stargazers_with_timestamps(&client, "glendc", "github-rust").unwrap();
+    assert_eq!(stargazers[0].user.login, "glendc");
+    assert_eq!(stargazers[0].starred_at.to_rfc3339(), "2015-01-01T15:00:00+00:00");
+    // The Accept header given via `opts` replaces the default one.
+    assert_eq!(accept_headers(&server), vec!["application/vnd.github.v3.star+json"]);
+}
+
+#[test]
+fn lists_starred_repos() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/users/glendc/starred", MockResponse::json(STARRED));
+    let client = server.client("glendc");
+
+    let (repos, _) = list_my_starred_repos(&client).unwrap();
+    assert_eq!(repos[0].name, "github-rust");
+
+    let (repos, _) = list_starred_repos_with_timestamps(&client, "glendc").unwrap();
+    assert_eq!(repos[0].repo.name, "github-rust");
+    assert_eq!(accept_headers(&server), vec!["application/vnd.github.v3.star+json"]);
+}
+
+#[test]
+fn stars_and_unstars_a_repo() {
+    let server = MockServer::start().unwrap();
+    let path = "/user/starred/glendc/github-rust";
+    server.route(Method::Get, path, MockResponse::no_content());
+    server.route(Method::Put, path, MockResponse::no_content());
+    server.route(Method::Delete, path, MockResponse::no_content());
+    let client = server.client("glendc");
+
+    assert!(is_starred(&client, "glendc", "github-rust").unwrap());
+    assert!(!is_starred(&client, "glendc", "other").unwrap());
+    star_repo(&client, "glendc", "github-rust").unwrap();
+    assert_eq!(server.requests().last().unwrap().method, "PUT");
+    unstar_repo(&client, "glendc", "github-rust").unwrap();
+    assert_eq!(server.requests().last().unwrap().method, "DELETE");
+}
+
+#[test]
+fn gives_other_failures_checking_a_star() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/user/starred/glendc/github-rust",
+        MockResponse::json(r#"{"message":"Requires authentication"}"#).status(401));
+    let client = server.client("glendc");
+
+    match is_starred(&client, "glendc", "github-rust") {
+        Err(ClientError::Http(ref e)) => assert_eq!(e.code, ErrorStatus::Unauthorized),
+        other => panic!("expected a 401, got {:?}", other),
+    }
+}
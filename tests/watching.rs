@@ -0,0 +1,64 @@
+extern crate github;
+
+use github::error::{ClientError, ErrorStatus};
+use github::http::Method;
+use github::mock::{MockResponse, MockServer};
+use github::activity::watching::*;
+
+/// The subscription of a user to a repository.
+static SUBSCRIPTION: &'static str = r#"{
+    "subscribed": true,
+    "ignored": false,
+    "reason": null,
+    "created_at": "2015-01-01T15:00:00Z",
+    "url": "https://api.github.com/repos/glendc/github-rust/subscription",
+    "repository_url": "https://api.github.com/repos/glendc/github-rust"
+}"#;
+
+#[test]
+fn lists_watchers_and_watched_repos() {
+    let server = MockServer::start().unwrap();
+    server.route(Method::Get, "/repos/glendc/github-rust/subscribers",
+        MockResponse::json(r#"[{"id":1,"login":"glendc"}]"#));
+    server.route(Method::Get, "/users/glendc/subscriptions",
+        MockResponse::json(r#"[{"id":42,"name":"github-rust","url":"https://api.github.com/repos/glendc/github-rust"}]"#));
+    let client = server.client("glendc");
+
+    let (users, _) = list_my_watchers(&client, "github-rust").unwrap();
+    assert_eq!(users[0].login, "glendc");
+    let (repos, _) = list_my_watched_repos(&client).unwrap();
+    assert_eq!(repos[0].name, "github-rust");
+}
+
+#[test]
+fn manages_a_repo_subscription() {
+    let server = MockServer::start().unwrap();
+    let path = "/repos/glendc/github-rust/subscription";
+    server.route(Method::Get, path, MockResponse::json(SUBSCRIPTION));
+    server.route(Method::Put, path, MockResponse::json(SUBSCRIPTION));
+    server.route(Method::Delete, path, MockResponse::no_content());
+    let client = server.client("glendc");
+
+    let (subscription, _) = get_repo_subscription(&client, "glendc", "github-rust").unwrap();
+    assert!(subscription.subscribed);
+    assert!(!subscription.ignored);
+    assert_eq!(subscription.created_at.unwrap().to_rfc3339(), "2015-01-01T15:00:00+00:00");
+
+    set_repo_subscription(&client, "glendc", "github-rust", true, false).unwrap();
+    let body = server.requests().last().unwrap().body.clone();
+    assert_eq!(String::from_utf8(body).unwrap(), r#"{"subscribed":true,"ignored":false}"#);
+
+    delete_repo_subscription(&client, "glendc", "github-rust").unwrap();
+    assert_eq!(server.requests().last().unwrap().method, "DELETE");
+}
+
+#[test]
+fn gives_a_missing_subscription_as_not_found() {
+    let server = MockServer::start().unwrap();
+    let client = server.client("glendc");
+
+    match get_repo_subscription(&client, "glendc", "github-rust") {
+        Err(ClientError::Http(ref e)) => assert_eq!(e.code, ErrorStatus::NotFound),
+        other => panic!("expected a 404, got {:?}", other.map(|(subscription, _)| subscription)),
+    }
+}